@@ -0,0 +1,212 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
+
+use c3_lang_linearization::{c3_linearization, diamonds, diff, suggest_fix, C3Error, Class, C3};
+
+const USAGE: &str = "Usage: c3 <mro|check|diamonds|dot|mermaid> <file>
+       c3 diff <old> <new>
+
+Reads a hierarchy, one class per line, e.g. `ERC20: Context, IERC20`.
+Everything after `#` is a comment. Use `-` as the file to read from stdin.
+`diff` compares classes and their MROs, the format does not declare functions.
+
+Exit codes:
+  0  success
+  1  the hierarchy is inconsistent
+  2  invalid input";
+
+const SUCCESS: i32 = 0;
+const INCONSISTENT: i32 = 1;
+const INPUT_ERROR: i32 = 2;
+
+#[derive(Debug, PartialEq)]
+struct Failure {
+    code: i32,
+    message: String,
+}
+
+impl Failure {
+    fn input(message: String) -> Self {
+        Failure {
+            code: INPUT_ERROR,
+            message,
+        }
+    }
+}
+
+impl From<C3Error> for Failure {
+    fn from(error: C3Error) -> Self {
+        let code = match error {
+            C3Error::InconsistentMro(_) | C3Error::Unresolved(_) => INCONSISTENT,
+            _ => INPUT_ERROR,
+        };
+        Failure {
+            code,
+            message: error.to_string(),
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.as_slice() {
+        [command, old, new] if command == "diff" => {
+            read(old).and_then(|old| read(new).and_then(|new| run_diff(&old, &new)))
+        }
+        [command, file] => read(file).and_then(|text| run(command, &text)),
+        _ => Err(Failure::input(String::from(USAGE))),
+    };
+    match result {
+        Ok(output) => {
+            print!("{}", output);
+            process::exit(SUCCESS);
+        }
+        Err(failure) => {
+            eprintln!("{}", failure.message);
+            process::exit(failure.code);
+        }
+    }
+}
+
+fn read(file: &str) -> Result<String, Failure> {
+    let mut text = String::new();
+    let result = if file == "-" {
+        io::stdin().read_to_string(&mut text).map(|_| ())
+    } else {
+        fs::read_to_string(file).map(|content| text = content)
+    };
+    result.map_err(|error| Failure::input(format!("Cannot read {}: {}", file, error)))?;
+    Ok(text)
+}
+
+fn run(command: &str, text: &str) -> Result<String, Failure> {
+    let input: C3 = text.parse()?;
+    match command {
+        "mro" => {
+            let output = linearize(input)?;
+            let mut result = String::new();
+            for (class, path) in output.all_paths_str() {
+                result.push_str(&format!("{}: {}\n", class, path));
+            }
+            Ok(result)
+        }
+        "check" => {
+            let output = linearize(input)?;
+            Ok(format!("OK: {} classes\n", output.all_classes().len()))
+        }
+        "diamonds" => {
+            let mut result = String::new();
+            for diamond in diamonds(&input)? {
+                result.push_str(&format!("{}\n", diamond));
+            }
+            Ok(result)
+        }
+        "dot" => Ok(input.to_dot()?),
+        "mermaid" => Ok(input.to_mermaid()?),
+        _ => Err(Failure::input(format!(
+            "Unknown command {}.\n\n{}",
+            command, USAGE
+        ))),
+    }
+}
+
+/// Compare two versions of a hierarchy.
+fn run_diff(old: &str, new: &str) -> Result<String, Failure> {
+    let parse = |text: &str, version: &str| {
+        text.parse::<C3>().map_err(|error| {
+            let mut failure = Failure::from(error);
+            failure.message = format!("{} hierarchy: {}", version, failure.message);
+            failure
+        })
+    };
+    let old = parse(old, "Old")?;
+    let new = parse(new, "New")?;
+    Ok(diff(&old, &new)?.to_string())
+}
+
+/// Linearize the input. If a class is inconsistent, suggest how to fix it.
+fn linearize(input: C3) -> Result<C3, Failure> {
+    c3_linearization(input.clone()).map_err(|error| {
+        let mut failure = Failure::from(error.clone());
+        if let C3Error::InconsistentMro(conflict) = error {
+            let class = Class::from(conflict.class.as_str());
+            if let Ok(Some(fix)) = suggest_fix(&input, &class) {
+                failure.message.push_str(&format!("\nSuggestion: {}", fix));
+            }
+        }
+        failure
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HIERARCHY: &str = "
+        # Diamond.
+        A:
+        B: A
+        C: A
+        D: B, C
+    ";
+
+    #[test]
+    fn test_mro() {
+        assert_eq!(
+            run("mro", HIERARCHY),
+            Ok(String::from("A: A\nB: B, A\nC: C, A\nD: D, B, C, A\n"))
+        );
+        assert_eq!(run("check", HIERARCHY), Ok(String::from("OK: 4 classes\n")));
+        assert_eq!(
+            run("diamonds", HIERARCHY),
+            Ok(String::from(
+                "D reaches A through D -> B -> A, D -> C -> A; A is at position 4 of its MRO\n"
+            ))
+        );
+        assert!(run("dot", HIERARCHY).unwrap().starts_with("digraph C3 {"));
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let inconsistent = "A:\nB: A\nC: A, B";
+        let failure = run("check", inconsistent).unwrap_err();
+        assert_eq!(failure.code, INCONSISTENT);
+        assert!(failure
+            .message
+            .ends_with("\nSuggestion: declare parents of C as B, A to get path C, B, A"));
+        assert_eq!(run("check", "A: B").unwrap_err().code, INPUT_ERROR);
+        assert_eq!(run("check", "A B").unwrap_err().code, INPUT_ERROR);
+        assert_eq!(
+            run("check", "A:\nB A"),
+            Err(Failure::input(String::from(
+                "Line 2, column 3: expected `:`, found `A`"
+            )))
+        );
+        assert_eq!(run("unknown", HIERARCHY).unwrap_err().code, INPUT_ERROR);
+    }
+
+    #[test]
+    fn test_diff() {
+        let new = "A:\nB: A\nC: A\nD: C, B";
+        assert_eq!(
+            run_diff(HIERARCHY, new),
+            Ok(String::from(
+                "Parents of D: [B, C] -> [C, B]\n\
+                 MRO of D: [D, B, C, A] -> [D, C, B, A]\n"
+            ))
+        );
+        assert_eq!(
+            run_diff(HIERARCHY, HIERARCHY),
+            Ok(String::from("No changes\n"))
+        );
+        assert_eq!(
+            run_diff(HIERARCHY, "A B"),
+            Err(Failure::input(String::from(
+                "New hierarchy: Line 1, column 3: expected `:`, found `B`"
+            )))
+        );
+    }
+}
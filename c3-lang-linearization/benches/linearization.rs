@@ -0,0 +1,82 @@
+use c3_lang_linearization::{c3_linearization, verify, C3};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Binary tree of classes, in which every class also inherits one of ten shared mixins,
+/// so there are many diamonds. Classes are declared from leaves to the root.
+fn diamonds(size: u32) -> C3<u32> {
+    let mut c3 = C3::new();
+    let mixins = 10;
+    for n in (mixins..size).rev() {
+        let mut parents = vec![];
+        if n / 2 >= mixins {
+            parents.push(n / 2);
+        }
+        parents.push(n % mixins);
+        c3.add(n, parents);
+    }
+    for n in 0..mixins {
+        c3.add(n, vec![]);
+    }
+    c3
+}
+
+/// Square number of independent chains of classes and a single class
+/// that inherits from the tips of all chains, so its merge has many long sets.
+fn chains(size: u32) -> C3<u32> {
+    let mut c3 = C3::new();
+    let length = (size as f64).sqrt() as u32;
+    let mut tips = vec![];
+    for chain in 0..length {
+        let first = chain * length;
+        c3.add(first, vec![]);
+        for n in first + 1..first + length {
+            c3.add(n, vec![n - 1]);
+        }
+        tips.push(first + length - 1);
+    }
+    c3.add(length * length, tips);
+    c3
+}
+
+/// Every class inherits from three previous classes, so lineages overlap a lot.
+fn overlapping(size: u32) -> C3<u32> {
+    let mut c3 = C3::new();
+    for n in 0..size {
+        c3.add(n, (n.saturating_sub(3)..n).rev().collect());
+    }
+    c3
+}
+
+fn bench_linearization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("c3_linearization");
+    group.sample_size(10);
+    for size in [1_000, 4_000, 16_000] {
+        let input = diamonds(size);
+        group.bench_with_input(BenchmarkId::new("diamonds", size), &input, |b, input| {
+            b.iter(|| c3_linearization(black_box(input.clone())).unwrap())
+        });
+        let input = chains(size);
+        group.bench_with_input(BenchmarkId::new("chains", size), &input, |b, input| {
+            b.iter(|| c3_linearization(black_box(input.clone())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    group.sample_size(10);
+    for size in [100, 250, 500] {
+        let input = overlapping(size);
+        let output = c3_linearization(input.clone()).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("overlapping", size),
+            &(input, output),
+            |b, (input, output)| b.iter(|| verify(black_box(input), black_box(output)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_linearization, bench_verify);
+criterion_main!(benches);
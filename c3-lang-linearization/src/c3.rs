@@ -1,95 +1,188 @@
-use std::collections::HashMap;
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use core::fmt::Debug;
 
-use crate::{id::Id, split_coma};
+use crate::{
+    c3_linearization::merge, id::Id, join, ordered_map::OrderedMap, parse_class, parse_parents,
+    validation::check_class,
+};
 
-use super::{C3Error, Sets};
+use super::{C3Error, Invalid, Problem, Sets};
 
 pub type Class = Id;
 pub type Fn = Id;
 pub type Var = Id;
 
+/// Type that can identify classes, functions or variables in `C3`, e.g. `Id`, integers,
+/// interned symbols or own enums. Implemented for every type with the required traits.
+/// Types without `Ord`, like `syn::Path`, can't be keys. Intern them or map them to an `Id`.
+pub trait Key: Clone + Eq + Ord + Debug {
+    /// Text used for the key in errors and reports, the `Debug` output.
+    fn label(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl<T: Clone + Eq + Ord + Debug> Key for T {}
+
+/// Function or variable visible in a class, with classes that declare it.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct C3 {
-    /// HashMap that maps base classes to path list.
-    classes: HashMap<Class, Vec<Class>>,
-    functions: HashMap<Class, Vec<Fn>>,
-    variables: HashMap<Class, Vec<Var>>,
+pub struct Member<K, M = K> {
+    pub name: M,
+    /// Declaring classes in the path order. The first one wins, the rest is shadowed.
+    pub declared_in: Vec<K>,
 }
 
-impl C3 {
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(deserialize = "K: serde::Deserialize<'de>, M: serde::Deserialize<'de>"))
+)]
+/// Classes identified by `K`, with functions and variables identified by `M`.
+pub struct C3<K: Key = Id, M: Key = K> {
+    /// Map of base classes to path list, kept in the declaration order.
+    classes: OrderedMap<K, Vec<K>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    functions: OrderedMap<K, Vec<M>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    variables: OrderedMap<K, Vec<M>>,
+}
+
+impl<K: Key, M: Key> Default for C3<K, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key, M: Key> C3<K, M> {
     /// Build new empty instance.
     pub fn new() -> Self {
         Self {
-            classes: HashMap::new(),
-            functions: HashMap::new(),
-            variables: HashMap::new(),
+            classes: OrderedMap::new(),
+            functions: OrderedMap::new(),
+            variables: OrderedMap::new(),
         }
     }
 
-    /// Add new class via strings.
-    pub fn add(&mut self, base: Class, path: Vec<Class>) {
+    /// Add new class.
+    pub fn add(&mut self, base: K, path: Vec<K>) {
         self.classes.insert(base, path);
     }
 
-    /// Add new class via strings.
-    pub fn add_class_str(&mut self, base: &str, parents: &str) {
-        let input: Vec<Class> = split_coma(parents).into_iter().map(Class::from).collect();
-        self.add(Class::from(base), input);
+    /// Add new class to already linearized collection.
+    /// Only the path of the new class is computed, using paths of its parents.
+    pub fn add_linearized(&mut self, base: K, parents: Vec<K>) -> Result<Vec<K>, C3Error> {
+        let mut problems = check_class(self, &base, &parents);
+        if self.contains(&base) {
+            problems.insert(
+                0,
+                Invalid {
+                    class: base.label(),
+                    problem: Problem::AlreadyDeclared,
+                },
+            );
+        }
+        if !problems.is_empty() {
+            return Err(C3Error::Invalid(problems));
+        }
+        let sets = self.sets_for(parents)?;
+        let path = merge(&base, sets)?;
+        self.add(base, path.clone());
+        Ok(path)
     }
 
-    /// Remove an element. Fails if element doesn't exists.
-    pub fn remove(&mut self, base: &Class) -> Result<(), C3Error> {
-        match self.classes.remove(base) {
-            Some(_) => Ok(()),
-            None => Err(C3Error::BaseClassDoesNotExists(base.clone().into())),
+    /// Remove an element. Fails if element doesn't exists
+    /// or other classes depend on it.
+    pub fn remove(&mut self, base: &K) -> Result<(), C3Error> {
+        let dependents = self.dependents(base)?;
+        if !dependents.is_empty() {
+            return Err(C3Error::HasDependents(
+                base.label(),
+                dependents.iter().map(K::label).collect(),
+            ));
         }
+        self.remove_class(base);
+        Ok(())
     }
 
-    /// Returns all base classes as a vector Classes.
-    pub fn all_classes(&self) -> Vec<Class> {
-        let mut keys: Vec<Class> = self.classes.keys().cloned().collect();
-        keys.sort();
-        keys
+    /// Remove an element together with all classes that depend on it.
+    /// Returns removed classes. Fails if element doesn't exists.
+    pub fn remove_cascade(&mut self, base: &K) -> Result<Vec<K>, C3Error> {
+        let mut removed = self.dependents(base)?;
+        removed.insert(0, base.clone());
+        for class in &removed {
+            self.remove_class(class);
+        }
+        Ok(removed)
+    }
+
+    /// Remove the class with its functions and variables.
+    fn remove_class(&mut self, class: &K) {
+        self.classes.remove(class);
+        self.functions.remove(class);
+        self.variables.remove(class);
+    }
+
+    /// Return other classes that inherit from a given class, directly or not,
+    /// in the declaration order. Works for both declared and linearized paths.
+    fn dependents(&self, base: &K) -> Result<Vec<K>, C3Error> {
+        let descendants = self.descendants(base)?;
+        let dependents = self
+            .all_classes()
+            .into_iter()
+            .filter(|class| descendants.contains(class))
+            .collect();
+        Ok(dependents)
+    }
+
+    /// Returns all base classes in the declaration order.
+    pub fn all_classes(&self) -> Vec<K> {
+        self.classes.keys().cloned().collect()
     }
 
     /// Returns all base classes as a vector of Strings.
     pub fn all_classes_str(&self) -> Vec<String> {
         self.all_classes()
             .into_iter()
-            .map(|class| class.into())
+            .map(|class| class.label())
             .collect()
     }
 
+    /// Check if the class is declared.
+    pub fn contains(&self, base: &K) -> bool {
+        self.classes.contains_key(base)
+    }
+
     /// Check if the collection is empty.
     pub fn is_empty(&self) -> bool {
         self.classes.is_empty()
     }
 
+    /// Replace registered functions and variables with the ones from `other`.
+    pub(crate) fn copy_members(&mut self, other: &C3<K, M>) {
+        self.functions = other.functions.clone();
+        self.variables = other.variables.clone();
+    }
+
     /// Return list of parents for a given base class.
-    pub fn path(&self, base: &Class) -> Result<Vec<Class>, C3Error> {
+    pub fn path(&self, base: &K) -> Result<Vec<K>, C3Error> {
         match self.classes.get(base) {
             Some(path) => Ok(path.clone()),
-            None => Err(C3Error::BaseClassDoesNotExists(base.clone().into())),
+            None => Err(C3Error::BaseClassDoesNotExists(base.label())),
         }
     }
 
     /// Retrun list of paths for all base classes in the string format.
     pub fn all_paths_str(&self) -> Vec<(String, String)> {
         let mut paths = vec![];
-        for (base, path) in &self.classes {
-            paths.push((
-                base.clone().into(),
-                path.iter()
-                    .map(|class| class.clone().into())
-                    .collect::<Vec<String>>()
-                    .join(", "),
-            ));
+        for (base, path) in self.classes.iter() {
+            paths.push((base.label(), join(path)));
         }
         paths
     }
 
     /// Prepare sets for the merge function.
-    pub fn sets_for(&self, bases: Vec<Class>) -> Result<Sets<Class>, C3Error> {
+    pub fn sets_for(&self, bases: Vec<K>) -> Result<Sets<K>, C3Error> {
         let mut sets = Sets::new();
         for base in &bases {
             let path = self.path(base)?;
@@ -103,97 +196,427 @@ impl C3 {
         Ok(sets)
     }
 
-    pub fn register_fn(&mut self, class: Class, fun: Fn) {
-        self.functions.entry(class).or_insert(vec![]).push(fun);
+    pub fn register_fn(&mut self, class: K, fun: M) {
+        self.functions.get_or_default(class).push(fun);
     }
 
-    pub fn register_fns(&mut self, class: Class, funs: Vec<Fn>) {
+    pub fn register_fns(&mut self, class: K, funs: Vec<M>) {
         for fun in funs {
             self.register_fn(class.clone(), fun);
         }
     }
 
-    pub fn register_fn_str(&mut self, class: &str, function: &str) {
-        self.register_fn(Class::from(class), Fn::from(function))
-    }
-
-    pub fn functions(&self, class: &Class) -> Vec<Fn> {
-        let path: Vec<Class> = self.path(class).unwrap();
-        let mut functions: Vec<Fn> = vec![];
+    /// Return sorted names of all functions visible in a class.
+    pub fn functions(&self, class: &K) -> Result<Vec<M>, C3Error> {
+        let path: Vec<K> = self.path(class)?;
+        let mut functions: Vec<M> = vec![];
         for class in path {
-            let mut list: Vec<Fn> = self.functions.get(&class).cloned().unwrap_or_default();
+            let mut list: Vec<M> = self.functions.get(&class).cloned().unwrap_or_default();
             functions.append(&mut list);
         }
         functions.sort();
         functions.dedup();
-        functions
+        Ok(functions)
+    }
+
+    /// Return the class which implementation runs when the function is called on `class`.
+    /// `None` if no class in the path implements it.
+    pub fn resolve(&self, class: &K, fun: &M) -> Result<Option<K>, C3Error> {
+        Ok(self.resolve_chain(class, fun)?.into_iter().next())
     }
 
-    pub fn functions_str(&self, class: &str) -> Vec<String> {
-        self.functions(&Class::from(class))
+    /// Return all classes implementing the function in the order
+    /// the calls to `super_` walk them, starting from the one that wins.
+    pub fn resolve_chain(&self, class: &K, fun: &M) -> Result<Vec<K>, C3Error> {
+        let chain = self
+            .path(class)?
             .into_iter()
-            .map(|x| x.to_string())
-            .collect()
+            .filter(|class| {
+                self.functions
+                    .get(class)
+                    .is_some_and(|functions| functions.contains(fun))
+            })
+            .collect();
+        Ok(chain)
     }
 
-    pub fn register_var(&mut self, class: Class, var: Var) {
-        self.variables.entry(class).or_insert(vec![]).push(var);
+    /// Return functions visible in a class with their declaring classes.
+    /// Functions are ordered by the path, starting from the class itself,
+    /// and by the registration order within a single class.
+    pub fn inherited_functions(&self, class: &K) -> Result<Vec<Member<K, M>>, C3Error> {
+        self.members(class, &self.functions)
     }
 
-    pub fn register_vars(&mut self, class: Class, vars: Vec<Var>) {
-        for var in vars {
-            self.register_var(class.clone(), var);
+    /// Return variables visible in a class with their declaring classes.
+    /// Ordered the same way as `inherited_functions`.
+    pub fn inherited_variables(&self, class: &K) -> Result<Vec<Member<K, M>>, C3Error> {
+        self.members(class, &self.variables)
+    }
+
+    fn members(
+        &self,
+        class: &K,
+        registry: &OrderedMap<K, Vec<M>>,
+    ) -> Result<Vec<Member<K, M>>, C3Error> {
+        let mut members: Vec<Member<K, M>> = vec![];
+        for class in self.path(class)? {
+            for name in registry.get(&class).into_iter().flatten() {
+                match members.iter_mut().find(|member| &member.name == name) {
+                    Some(member) => {
+                        if !member.declared_in.contains(&class) {
+                            member.declared_in.push(class.clone());
+                        }
+                    }
+                    None => members.push(Member {
+                        name: name.clone(),
+                        declared_in: vec![class.clone()],
+                    }),
+                }
+            }
         }
+        Ok(members)
     }
 
-    pub fn register_var_str(&mut self, class: &str, variable: &str) {
-        self.register_var(Class::from(class), Var::from(variable))
+    pub fn register_var(&mut self, class: K, var: M) {
+        self.variables.get_or_default(class).push(var);
     }
 
-    pub fn variables(&self, class: &Class) -> Vec<Var> {
-        let path: Vec<Class> = self.path(class).unwrap();
-        let mut varialbes: Vec<Var> = vec![];
+    pub fn register_vars(&mut self, class: K, vars: Vec<M>) {
+        for var in vars {
+            self.register_var(class.clone(), var);
+        }
+    }
+
+    /// Return sorted names of all variables visible in a class.
+    pub fn variables(&self, class: &K) -> Result<Vec<M>, C3Error> {
+        let path: Vec<K> = self.path(class)?;
+        let mut varialbes: Vec<M> = vec![];
         for class in path {
-            let mut list: Vec<Var> = self.variables.get(&class).cloned().unwrap_or_default();
+            let mut list: Vec<M> = self.variables.get(&class).cloned().unwrap_or_default();
             varialbes.append(&mut list);
         }
         varialbes.sort();
         varialbes.dedup();
-        varialbes
+        Ok(varialbes)
     }
+}
 
-    pub fn varialbes_str(&self, class: &str) -> Vec<String> {
-        self.variables(&Class::from(class))
-            .into_iter()
-            .map(|x| x.to_string())
-            .collect()
+impl C3 {
+    /// Add new class via strings. Fails if any name is not a valid identifier.
+    pub fn add_class_str(&mut self, base: &str, parents: &str) -> Result<(), C3Error> {
+        self.add(parse_class(base)?, parse_parents(parents)?);
+        Ok(())
+    }
+
+    /// Fails if any name is not a valid identifier.
+    pub fn register_fn_str(&mut self, class: &str, function: &str) -> Result<(), C3Error> {
+        self.register_fn(Class::new(class)?, Fn::new(function)?);
+        Ok(())
+    }
+
+    pub fn functions_str(&self, class: &str) -> Result<Vec<String>, C3Error> {
+        let functions = self.functions(&Class::from(class))?;
+        Ok(functions.into_iter().map(|x| x.to_string()).collect())
+    }
+
+    /// Fails if any name is not a valid identifier.
+    pub fn register_var_str(&mut self, class: &str, variable: &str) -> Result<(), C3Error> {
+        self.register_var(Class::new(class)?, Var::new(variable)?);
+        Ok(())
+    }
+
+    pub fn varialbes_str(&self, class: &str) -> Result<Vec<String>, C3Error> {
+        let variables = self.variables(&Class::from(class))?;
+        Ok(variables.into_iter().map(|x| x.to_string()).collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::C3;
+    use super::{Class, Fn, Member, C3};
+    use crate::{fixtures::classes, C3Error};
 
     #[test]
     fn test_c3() {
         let mut c3 = C3::new();
-        c3.add_class_str("A", "A");
-        c3.add_class_str("B", "B, A");
-        c3.add_class_str("K1", "K1, K2, A");
+        c3.add_class_str("A", "A").unwrap();
+        c3.add_class_str("B", "B, A").unwrap();
+        c3.add_class_str("K1", "K1, K2, A").unwrap();
 
         assert_eq!(c3.all_classes_str(), vec!["A", "B", "K1"]);
 
-        c3.register_fn_str("A", "foo");
-        c3.register_fn_str("A", "bar");
-        c3.register_fn_str("B", "bar");
-        assert_eq!(c3.functions_str("A"), vec!["bar", "foo"]);
-        assert_eq!(c3.functions_str("B"), vec!["bar", "foo"]);
+        c3.register_fn_str("A", "foo").unwrap();
+        c3.register_fn_str("A", "bar").unwrap();
+        c3.register_fn_str("B", "bar").unwrap();
+        assert_eq!(c3.functions_str("A").unwrap(), vec!["bar", "foo"]);
+        assert_eq!(c3.functions_str("B").unwrap(), vec!["bar", "foo"]);
 
-        c3.register_var_str("A", "x");
-        c3.register_var_str("B", "y");
-        assert_eq!(c3.varialbes_str("A"), vec!["x"]);
-        assert_eq!(c3.varialbes_str("B"), vec!["x", "y"]);
+        c3.register_var_str("A", "x").unwrap();
+        c3.register_var_str("B", "y").unwrap();
+        assert_eq!(c3.varialbes_str("A").unwrap(), vec!["x"]);
+        assert_eq!(c3.varialbes_str("B").unwrap(), vec!["x", "y"]);
+
+        let error = C3Error::BaseClassDoesNotExists(String::from("X"));
+        assert_eq!(c3.functions_str("X"), Err(error.clone()));
+        assert_eq!(c3.varialbes_str("X"), Err(error));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut c3 = C3::new();
+        c3.add_class_str("A", "A").unwrap();
+        c3.add_class_str("B", "B, A").unwrap();
+        c3.add_class_str("C", "C, A").unwrap();
+        c3.add_class_str("D", "D, B, C, A").unwrap();
+        c3.register_fn_str("A", "foo").unwrap();
+        c3.register_fn_str("C", "foo").unwrap();
+        c3.register_fn_str("A", "bar").unwrap();
+
+        let class = |name: &str| Class::from(name);
+        let foo = Fn::from("foo");
+        assert_eq!(c3.resolve(&class("D"), &foo), Ok(Some(class("C"))));
+        assert_eq!(c3.resolve(&class("B"), &foo), Ok(Some(class("A"))));
+        assert_eq!(c3.resolve(&class("D"), &Fn::from("baz")), Ok(None));
+        assert_eq!(
+            c3.resolve_chain(&class("D"), &foo),
+            Ok(vec![class("C"), class("A")])
+        );
+        assert_eq!(
+            c3.resolve(&class("X"), &foo),
+            Err(C3Error::BaseClassDoesNotExists(String::from("X")))
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    enum Contract {
+        Context,
+        Erc20,
+        Token,
+        Unknown,
+    }
+
+    #[test]
+    fn test_custom_keys() {
+        let mut c3: C3<Contract, &str> = C3::new();
+        c3.add(Contract::Context, vec![Contract::Context]);
+        c3.add(Contract::Erc20, vec![Contract::Erc20, Contract::Context]);
+        c3.add(
+            Contract::Token,
+            vec![Contract::Token, Contract::Erc20, Contract::Context],
+        );
+        c3.register_fn(Contract::Context, "caller");
+        c3.register_fn(Contract::Erc20, "transfer");
+        c3.register_var(Contract::Erc20, "balance");
+
+        assert_eq!(
+            c3.resolve(&Contract::Token, &"caller"),
+            Ok(Some(Contract::Context))
+        );
+        assert_eq!(
+            c3.functions(&Contract::Token),
+            Ok(vec!["caller", "transfer"])
+        );
+        assert_eq!(c3.variables(&Contract::Context), Ok(vec![]));
+        assert_eq!(
+            c3.path(&Contract::Unknown),
+            Err(C3Error::BaseClassDoesNotExists(String::from("Unknown")))
+        );
+    }
+
+    #[test]
+    fn test_foreign_keys() {
+        // Module paths split into segments, as in `syn::Path`, without a wrapper type.
+        let mut c3: C3<Vec<&str>> = C3::new();
+        c3.add(vec!["access", "Ownable"], vec![]);
+        c3.add(vec!["Token"], vec![vec!["access", "Ownable"]]);
+        assert_eq!(c3.path(&vec!["Token"]), Ok(vec![vec!["access", "Ownable"]]));
+        assert_eq!(
+            c3.path(&vec!["Unknown"]),
+            Err(C3Error::BaseClassDoesNotExists(String::from(
+                "[\"Unknown\"]"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_inherited_members() {
+        let mut c3 = C3::new();
+        c3.add_class_str("Context", "Context").unwrap();
+        c3.add_class_str("ERC20", "ERC20, Context").unwrap();
+        c3.add_class_str("Token", "Token, ERC20, Context").unwrap();
+        c3.register_fn_str("Context", "caller").unwrap();
+        c3.register_fn_str("ERC20", "transfer").unwrap();
+        c3.register_fn_str("ERC20", "caller").unwrap();
+        c3.register_fn_str("Token", "transfer").unwrap();
+        c3.register_var_str("ERC20", "total_supply").unwrap();
+        c3.register_var_str("ERC20", "balance").unwrap();
+
+        let member = |name: &str, declared_in: &[&str]| Member {
+            name: Fn::from(name),
+            declared_in: declared_in
+                .iter()
+                .map(|&class| Class::from(class))
+                .collect(),
+        };
+        assert_eq!(
+            c3.inherited_functions(&Class::from("Token")),
+            Ok(vec![
+                member("transfer", &["Token", "ERC20"]),
+                member("caller", &["ERC20", "Context"]),
+            ])
+        );
+        assert_eq!(
+            c3.inherited_variables(&Class::from("Token")),
+            Ok(vec![
+                member("total_supply", &["ERC20"]),
+                member("balance", &["ERC20"]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_declaration_order() {
+        let mut c3 = C3::new();
+        c3.add_class_str("K1", "K1, K2, A").unwrap();
+        c3.add_class_str("B", "B, A").unwrap();
+        c3.add_class_str("A", "A").unwrap();
+
+        assert_eq!(c3.all_classes_str(), vec!["K1", "B", "A"]);
+        assert_eq!(
+            c3.all_paths_str(),
+            vec![
+                (String::from("K1"), String::from("K1, K2, A")),
+                (String::from("B"), String::from("B, A")),
+                (String::from("A"), String::from("A")),
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut c3 = C3::new();
+        c3.add_class_str("B", "B, A").unwrap();
+        c3.add_class_str("A", "A").unwrap();
+        c3.register_fn_str("A", "foo").unwrap();
+        c3.register_var_str("B", "x").unwrap();
+
+        let json = serde_json::to_string(&c3).unwrap();
+        assert_eq!(
+            json,
+            r#"{"classes":{"B":["B","A"],"A":["A"]},"functions":{"A":["foo"]},"variables":{"B":["x"]}}"#
+        );
+        let from_json: C3 = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json, c3);
+        assert_eq!(from_json.all_classes_str(), vec!["B", "A"]);
+
+        let from_toml: C3 = toml::from_str(&toml::to_string(&c3).unwrap()).unwrap();
+        assert_eq!(from_toml, c3);
+
+        let from_toml: C3 = toml::from_str("[classes]\nA = []\nB = [\"A\"]\n").unwrap();
+        assert_eq!(from_toml.all_paths_str().len(), 2);
+
+        let error = serde_json::from_str::<C3>(r#"{"classes":{"ERC-20":[]}}"#).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("`ERC-20` is not a valid identifier"));
+    }
+
+    #[test]
+    fn test_add_class_str() {
+        let mut c3 = C3::new();
+        c3.add_class_str(" B ", "A,C").unwrap();
+        assert_eq!(
+            c3.all_paths_str(),
+            vec![(String::from("B"), String::from("A, C"))]
+        );
+        assert!(c3.add_class_str("ERC-20", "A").is_err());
+        assert!(c3.add_class_str("A B", "").is_err());
+        assert_eq!(
+            c3.register_fn_str("B", "transfer-from"),
+            Err(C3Error::InvalidId(String::from("transfer-from")))
+        );
+        assert!(c3.register_var_str("B", "r#type").is_ok());
     }
 
-    // TODO: More tests.
+    #[test]
+    fn test_add_linearized() {
+        let mut c3 = C3::new();
+        c3.add_class_str("A", "A").unwrap();
+        c3.add_class_str("B", "B, A").unwrap();
+        c3.add_class_str("C", "C, A").unwrap();
+
+        let path = c3.add_linearized(Class::from("D"), classes(&["B", "C"]));
+        let target = classes(&["D", "B", "C", "A"]);
+        assert_eq!(path.unwrap(), target);
+        assert_eq!(c3.path(&Class::from("D")).unwrap(), target);
+
+        let error = c3
+            .add_linearized(Class::from("D"), vec![Class::from("X")])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid hierarchy: D is already declared; D has undeclared parent X"
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut c3 = C3::new();
+        c3.add_class_str("A", "A").unwrap();
+        c3.add_class_str("B", "B, A").unwrap();
+        c3.add_class_str("C", "C, B, A").unwrap();
+        c3.add_class_str("D", "D").unwrap();
+        c3.register_fn_str("C", "burn").unwrap();
+        c3.register_var_str("C", "supply").unwrap();
+        c3.register_fn_str("D", "mint").unwrap();
+
+        assert_eq!(
+            c3.remove(&Class::from("A")),
+            Err(C3Error::HasDependents(
+                String::from("A"),
+                vec![String::from("B"), String::from("C")]
+            ))
+        );
+        assert_eq!(c3.remove(&Class::from("D")), Ok(()));
+        assert_eq!(
+            c3.remove(&Class::from("D")),
+            Err(C3Error::BaseClassDoesNotExists(String::from("D")))
+        );
+
+        let removed = c3.remove_cascade(&Class::from("B")).unwrap();
+        assert_eq!(removed, vec![Class::from("B"), Class::from("C")]);
+        assert_eq!(c3.all_classes_str(), vec!["A"]);
+
+        // Members of removed classes are not inherited by classes declared again.
+        c3.add_class_str("C", "C, A").unwrap();
+        c3.add_class_str("D", "D").unwrap();
+        assert_eq!(c3.functions_str("C"), Ok(vec![]));
+        assert_eq!(c3.varialbes_str("C"), Ok(vec![]));
+        assert_eq!(c3.functions_str("D"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_remove_declared() {
+        let mut c3 = C3::new();
+        c3.add_class_str("A", "").unwrap();
+        c3.add_class_str("B", "A").unwrap();
+        c3.add_class_str("C", "B").unwrap();
+        c3.add_class_str("D", "").unwrap();
+
+        assert_eq!(
+            c3.remove(&Class::from("A")),
+            Err(C3Error::HasDependents(
+                String::from("A"),
+                vec![String::from("B"), String::from("C")]
+            ))
+        );
+        let removed = c3.remove_cascade(&Class::from("A")).unwrap();
+        assert_eq!(
+            removed,
+            vec![Class::from("A"), Class::from("B"), Class::from("C")]
+        );
+        assert_eq!(c3.all_classes_str(), vec!["D"]);
+        assert!(crate::c3_linearization(c3).is_ok());
+    }
 }
@@ -1,37 +1,156 @@
-use std::fmt::Debug;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::{vec, vec::Vec};
 
-use super::{is_subset, C3Error, Sets, C3};
+use super::{
+    validate, C3Error, C3Merge, Key, LinearizationStrategy, Sets, Unresolved, UnresolvedReason, C3,
+};
 
 // TODO: Re-implement using single Classes.
-pub fn c3_linearization(mut input: C3) -> Result<C3, C3Error> {
+pub fn c3_linearization<K: Key, M: Key>(input: C3<K, M>) -> Result<C3<K, M>, C3Error> {
+    linearization_with(input, &C3Merge)
+}
+
+/// Linearize all classes using the given strategy.
+pub fn linearization_with<K: Key, M: Key, S: LinearizationStrategy>(
+    input: C3<K, M>,
+    strategy: &S,
+) -> Result<C3<K, M>, C3Error> {
+    validate(&input)?;
+    let classes = input.all_classes();
+
+    // Number of not yet linearized parents of every class.
+    let mut pending: BTreeMap<K, usize> = BTreeMap::new();
+    let mut children: BTreeMap<K, Vec<K>> = BTreeMap::new();
+    let mut queue: VecDeque<K> = VecDeque::new();
+    for class in &classes {
+        let parents = input.path(class)?;
+        if parents.is_empty() {
+            queue.push_back(class.clone());
+        }
+        pending.insert(class.clone(), parents.len());
+        for parent in parents {
+            children.entry(parent).or_default().push(class.clone());
+        }
+    }
+
     let mut output = C3::new();
-    loop {
-        let solved = output.all_classes();
-        let mut found = false;
-        for base in input.all_classes() {
-            let parents = input.path(&base)?;
-            if is_subset(&solved, &parents) {
-                let sets = output.sets_for(parents)?;
-                let solution = merge(&base, sets)?;
-                input.remove(&base)?;
-                output.add(base, solution);
-                found = true;
+    let mut solved = 0;
+    while let Some(base) = queue.pop_front() {
+        let path = strategy.linearize(&base, &input, &output)?;
+        output.add(base.clone(), path);
+        solved += 1;
+        for child in children.get(&base).into_iter().flatten() {
+            let count = pending.get_mut(child).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                queue.push_back(child.clone());
             }
         }
-        if !found {
-            break;
+    }
+
+    if solved == classes.len() {
+        let mut result = C3::new();
+        for class in classes {
+            let path = output.path(&class)?;
+            result.add(class, path);
+        }
+        Ok(result)
+    } else {
+        Err(C3Error::Unresolved(unresolved(&input, &output)))
+    }
+}
+
+/// Linearize only ancestors of the class.
+pub(crate) fn linearize_ancestors<K: Key, M: Key>(
+    input: &C3<K, M>,
+    class: &K,
+) -> Result<C3<K, M>, C3Error> {
+    let mut ancestors = C3::new();
+    let mut stack = input.path(class)?;
+    while let Some(ancestor) = stack.pop() {
+        if !ancestors.contains(&ancestor) {
+            let path = input.path(&ancestor)?;
+            stack.extend(path.iter().cloned());
+            ancestors.add(ancestor, path);
+        }
+    }
+    c3_linearization(ancestors)
+}
+
+/// Explain why every class left in `input` could not be linearized.
+fn unresolved<K: Key, M: Key>(input: &C3<K, M>, output: &C3<K, M>) -> Vec<Unresolved> {
+    let mut result = vec![];
+    for class in input.all_classes() {
+        if output.contains(&class) {
+            continue;
+        }
+        let reason = match find_cycle(input, &class) {
+            Some(cycle) => UnresolvedReason::Cycle(cycle.iter().map(K::label).collect()),
+            None => {
+                let parents = input.path(&class).unwrap_or_default();
+                let parent = parents
+                    .into_iter()
+                    .find(|parent| !output.contains(parent))
+                    .unwrap();
+                UnresolvedReason::UnresolvedParent(parent.label())
+            }
+        };
+        result.push(Unresolved {
+            class: class.label(),
+            reason,
+        });
+    }
+    result
+}
+
+/// Find a path of parents that leads from `start` back to itself.
+fn find_cycle<K: Key, M: Key>(input: &C3<K, M>, start: &K) -> Option<Vec<K>> {
+    let mut path = vec![start.clone()];
+    let mut visited = vec![];
+    if visit_parents(input, start, &mut path, &mut visited) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+fn visit_parents<K: Key, M: Key>(
+    input: &C3<K, M>,
+    start: &K,
+    path: &mut Vec<K>,
+    visited: &mut Vec<K>,
+) -> bool {
+    let current = path.last().unwrap().clone();
+    for parent in input.path(&current).unwrap_or_default() {
+        if &parent == start {
+            path.push(parent);
+            return true;
+        }
+        if visited.contains(&parent) {
+            continue;
         }
+        visited.push(parent.clone());
+        path.push(parent);
+        if visit_parents(input, start, path, visited) {
+            return true;
+        }
+        path.pop();
     }
-    Ok(output)
+    false
 }
 
-pub fn merge<T: Clone + Debug + Eq>(base: &T, mut sets: Sets<T>) -> Result<Vec<T>, C3Error> {
+pub fn merge<T: Key>(base: &T, mut sets: Sets<T>) -> Result<Vec<T>, C3Error> {
     let mut solutions = vec![base.clone()];
     loop {
         if sets.is_empty() {
             return Ok(solutions);
         }
-        let solution = sets.find_solution()?;
+        let solution = match sets.find_solution() {
+            Err(C3Error::NoMoreCandidates) => {
+                return Err(C3Error::InconsistentMro(sets.conflict(base)))
+            }
+            result => result?,
+        };
         solutions.push(solution);
     }
 }
@@ -39,20 +158,17 @@ pub fn merge<T: Clone + Debug + Eq>(base: &T, mut sets: Sets<T>) -> Result<Vec<T
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{fixtures::diamond, MroConflict, Unresolved, UnresolvedReason};
 
     #[test]
     fn test_c3() {
-        let mut input = C3::new();
-        input.add_class_str("A", "");
-        input.add_class_str("B", "A");
-        input.add_class_str("C", "A");
-        input.add_class_str("D", "B, C");
+        let input = diamond();
 
         let mut target = C3::new();
-        target.add_class_str("A", "A");
-        target.add_class_str("B", "B, A");
-        target.add_class_str("C", "C, A");
-        target.add_class_str("D", "D, B, C, A");
+        target.add_class_str("A", "A").unwrap();
+        target.add_class_str("B", "B, A").unwrap();
+        target.add_class_str("C", "C, A").unwrap();
+        target.add_class_str("D", "D, B, C, A").unwrap();
 
         assert_eq!(c3_linearization(input).unwrap(), target);
     }
@@ -60,35 +176,70 @@ mod tests {
     #[test]
     fn test_c3_second_example() {
         let mut input = C3::new();
-        input.add_class_str("Context", "");
-        input.add_class_str("IERC20", "");
-        input.add_class_str("IERC20Metadata", "IERC20");
-        input.add_class_str("IERC20Errors", "");
-        input.add_class_str("ERC20", "Context, IERC20Metadata, IERC20, IERC20Errors");
-        input.add_class_str("ERC20Burnable", "ERC20, Context");
-        input.add_class_str("ERC20Capped", "ERC20");
-        input.add_class_str("Ownable", "Context");
-        input.add_class_str("Plascoin", "ERC20Capped, ERC20Burnable, Ownable");
+        input.add_class_str("Context", "").unwrap();
+        input.add_class_str("IERC20", "").unwrap();
+        input.add_class_str("IERC20Metadata", "IERC20").unwrap();
+        input.add_class_str("IERC20Errors", "").unwrap();
+        input
+            .add_class_str("ERC20", "Context, IERC20Metadata, IERC20, IERC20Errors")
+            .unwrap();
+        input
+            .add_class_str("ERC20Burnable", "ERC20, Context")
+            .unwrap();
+        input.add_class_str("ERC20Capped", "ERC20").unwrap();
+        input.add_class_str("Ownable", "Context").unwrap();
+        input
+            .add_class_str("Plascoin", "ERC20Capped, ERC20Burnable, Ownable")
+            .unwrap();
 
         let mut target = C3::new();
-        target.add_class_str("Context", "Context");
-        target.add_class_str("IERC20", "IERC20");
-        target.add_class_str("IERC20Metadata", "IERC20Metadata, IERC20");
-        target.add_class_str("IERC20Errors", "IERC20Errors");
-        target.add_class_str(
-            "ERC20",
-            "ERC20, Context, IERC20Metadata, IERC20, IERC20Errors",
-        );
-        target.add_class_str(
-            "ERC20Burnable",
-            "ERC20Burnable, ERC20, Context, IERC20Metadata, IERC20, IERC20Errors",
-        );
-        target.add_class_str(
-            "ERC20Capped",
-            "ERC20Capped, ERC20, Context, IERC20Metadata, IERC20, IERC20Errors",
-        );
-        target.add_class_str("Ownable", "Ownable, Context");
-        target.add_class_str("Plascoin", "Plascoin, ERC20Capped, ERC20Burnable, ERC20, Ownable, Context, IERC20Metadata, IERC20, IERC20Errors");
+        target.add_class_str("Context", "Context").unwrap();
+        target.add_class_str("IERC20", "IERC20").unwrap();
+        target
+            .add_class_str("IERC20Metadata", "IERC20Metadata, IERC20")
+            .unwrap();
+        target
+            .add_class_str("IERC20Errors", "IERC20Errors")
+            .unwrap();
+        target
+            .add_class_str(
+                "ERC20",
+                "ERC20, Context, IERC20Metadata, IERC20, IERC20Errors",
+            )
+            .unwrap();
+        target
+            .add_class_str(
+                "ERC20Burnable",
+                "ERC20Burnable, ERC20, Context, IERC20Metadata, IERC20, IERC20Errors",
+            )
+            .unwrap();
+        target
+            .add_class_str(
+                "ERC20Capped",
+                "ERC20Capped, ERC20, Context, IERC20Metadata, IERC20, IERC20Errors",
+            )
+            .unwrap();
+        target.add_class_str("Ownable", "Ownable, Context").unwrap();
+        target.add_class_str("Plascoin", "Plascoin, ERC20Capped, ERC20Burnable, ERC20, Ownable, Context, IERC20Metadata, IERC20, IERC20Errors").unwrap();
+
+        let output = c3_linearization(input.clone()).unwrap();
+        assert_eq!(output.all_classes(), input.all_classes());
+        assert_eq!(output, target);
+    }
+
+    #[test]
+    fn test_c3_with_integer_keys() {
+        let mut input: C3<u32> = C3::new();
+        input.add(1, vec![]);
+        input.add(2, vec![1]);
+        input.add(3, vec![1]);
+        input.add(4, vec![2, 3]);
+
+        let mut target: C3<u32> = C3::new();
+        target.add(1, vec![1]);
+        target.add(2, vec![2, 1]);
+        target.add(3, vec![3, 1]);
+        target.add(4, vec![4, 2, 3, 1]);
 
         assert_eq!(c3_linearization(input).unwrap(), target);
     }
@@ -119,4 +270,64 @@ mod tests {
         let expected = vec!["Z", "K1", "C", "K3", "K2", "B", "A", "D", "E", "O"];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_inconsistent_mro() {
+        let mut input = C3::new();
+        input.add_class_str("O", "").unwrap();
+        input.add_class_str("A", "O").unwrap();
+        input.add_class_str("B", "O").unwrap();
+        input.add_class_str("X", "A, B").unwrap();
+        input.add_class_str("Y", "B, A").unwrap();
+        input.add_class_str("Z", "X, Y").unwrap();
+
+        let error = c3_linearization(input).unwrap_err();
+        let conflict = MroConflict {
+            class: String::from("Z"),
+            conflicting: vec![String::from("A"), String::from("B")],
+            remaining: vec![
+                vec![String::from("A"), String::from("B"), String::from("O")],
+                vec![String::from("B"), String::from("A"), String::from("O")],
+            ],
+        };
+        assert_eq!(error, C3Error::InconsistentMro(conflict));
+        assert_eq!(
+            error.to_string(),
+            "Cannot create a consistent method resolution order (MRO) for class Z: \
+             conflicting bases A, B; remaining sets [A, B, O], [B, A, O]"
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut input = C3::new();
+        input.add_class_str("O", "").unwrap();
+        input.add_class_str("A", "O, B").unwrap();
+        input.add_class_str("B", "A").unwrap();
+        input.add_class_str("C", "B").unwrap();
+
+        let cycle =
+            |path: &[&str]| UnresolvedReason::Cycle(path.iter().map(|x| x.to_string()).collect());
+        let error = c3_linearization(input).unwrap_err();
+        let target = vec![
+            Unresolved {
+                class: String::from("A"),
+                reason: cycle(&["A", "B", "A"]),
+            },
+            Unresolved {
+                class: String::from("B"),
+                reason: cycle(&["B", "A", "B"]),
+            },
+            Unresolved {
+                class: String::from("C"),
+                reason: UnresolvedReason::UnresolvedParent(String::from("B")),
+            },
+        ];
+        assert_eq!(error, C3Error::Unresolved(target));
+        assert_eq!(
+            error.to_string(),
+            "Cannot linearize classes: A is part of cycle A -> B -> A; \
+             B is part of cycle B -> A -> B; C depends on unresolved class B"
+        );
+    }
 }
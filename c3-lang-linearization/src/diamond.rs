@@ -0,0 +1,139 @@
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::{self, Display};
+
+use super::{c3_linearization, C3Error, Key, C3};
+
+/// Base class reachable from a class through more than one direct parent.
+/// Displayed with the 1-based position of the base in the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diamond<K> {
+    pub class: K,
+    pub base: K,
+    /// Shortest path from the class to the base through every parent that reaches it.
+    /// Paths are in the order of the parents and start with the class itself.
+    pub paths: Vec<Vec<K>>,
+    /// Index of the base in the linearized path of the class.
+    pub position: usize,
+}
+
+/// Find all diamonds in the declared hierarchy.
+/// Diamonds are ordered by class declaration and then by the position of the base.
+pub fn diamonds<K: Key, M: Key>(input: &C3<K, M>) -> Result<Vec<Diamond<K>>, C3Error> {
+    let output = c3_linearization(input.clone())?;
+    let mut result = vec![];
+    for class in input.all_classes() {
+        let mro = output.path(&class)?;
+        let mut shared: BTreeMap<K, Vec<Vec<K>>> = BTreeMap::new();
+        for parent in input.path(&class)? {
+            for (base, mut path) in shortest_paths(input, &parent) {
+                path.insert(0, class.clone());
+                shared.entry(base).or_default().push(path);
+            }
+        }
+        let mut found: Vec<Diamond<K>> = shared
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .map(|(base, paths)| Diamond {
+                class: class.clone(),
+                position: mro.iter().position(|x| x == &base).unwrap(),
+                base,
+                paths,
+            })
+            .collect();
+        found.sort_by_key(|diamond| diamond.position);
+        result.append(&mut found);
+    }
+    Ok(result)
+}
+
+/// Shortest path from `start` to itself and each of its ancestors.
+fn shortest_paths<K: Key, M: Key>(input: &C3<K, M>, start: &K) -> BTreeMap<K, Vec<K>> {
+    let mut paths: BTreeMap<K, Vec<K>> = BTreeMap::new();
+    paths.insert(start.clone(), vec![start.clone()]);
+    let mut queue: VecDeque<K> = VecDeque::new();
+    queue.push_back(start.clone());
+    while let Some(class) = queue.pop_front() {
+        let path = paths.get(&class).unwrap().clone();
+        for parent in input.path(&class).unwrap_or_default() {
+            if !paths.contains_key(&parent) {
+                let mut next = path.clone();
+                next.push(parent.clone());
+                paths.insert(parent.clone(), next);
+                queue.push_back(parent);
+            }
+        }
+    }
+    paths
+}
+
+impl<K: Key> Display for Diamond<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let paths: Vec<String> = self
+            .paths
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .map(K::label)
+                    .collect::<Vec<String>>()
+                    .join(" -> ")
+            })
+            .collect();
+        write!(
+            f,
+            "{} reaches {} through {}; {} is at position {} of its MRO",
+            self.class.label(),
+            self.base.label(),
+            paths.join(", "),
+            self.base.label(),
+            self.position + 1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::classes, Class};
+
+    #[test]
+    fn test_diamonds() {
+        let mut input = C3::new();
+        input.add_class_str("Context", "").unwrap();
+        input.add_class_str("IERC20", "").unwrap();
+        input.add_class_str("ERC20", "Context, IERC20").unwrap();
+        input.add_class_str("Ownable", "Context").unwrap();
+        input
+            .add_class_str("Token", "ERC20, Ownable, IERC20")
+            .unwrap();
+        input.add_class_str("Wrapped", "Token").unwrap();
+
+        let result = diamonds(&input).unwrap();
+        let target = vec![
+            Diamond {
+                class: Class::from("Token"),
+                base: Class::from("Context"),
+                paths: vec![
+                    classes(&["Token", "ERC20", "Context"]),
+                    classes(&["Token", "Ownable", "Context"]),
+                ],
+                position: 3,
+            },
+            Diamond {
+                class: Class::from("Token"),
+                base: Class::from("IERC20"),
+                paths: vec![
+                    classes(&["Token", "ERC20", "IERC20"]),
+                    classes(&["Token", "IERC20"]),
+                ],
+                position: 4,
+            },
+        ];
+        assert_eq!(result, target);
+        assert_eq!(
+            result[0].to_string(),
+            "Token reaches Context through Token -> ERC20 -> Context, \
+             Token -> Ownable -> Context; Context is at position 4 of its MRO"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+
+use super::{c3_linearization, join, C3Error, Key, C3};
+
+/// Differences between two versions of a hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HierarchyDiff<K, M = K> {
+    /// Classes only in the new version, in its declaration order.
+    pub added: Vec<K>,
+    /// Classes only in the old version, in its declaration order.
+    pub removed: Vec<K>,
+    /// Classes with different direct parents.
+    pub parents: Vec<Changed<K>>,
+    /// Classes with different paths.
+    pub paths: Vec<Changed<K>>,
+    /// Functions resolved to a different class.
+    pub moved: Vec<Moved<K, M>>,
+}
+
+/// Class with a list of classes that differs between versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changed<K> {
+    pub class: K,
+    pub old: Vec<K>,
+    pub new: Vec<K>,
+}
+
+/// Function of a class, which implementation is taken from another class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Moved<K, M = K> {
+    pub class: K,
+    pub function: M,
+    pub old: K,
+    pub new: K,
+}
+
+impl<K, M> HierarchyDiff<K, M> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.parents.is_empty()
+            && self.paths.is_empty()
+            && self.moved.is_empty()
+    }
+}
+
+/// Compare two declared hierarchies. Both of them must be linearizable.
+/// Changes of classes present in both versions are in the new declaration order.
+/// Only functions visible in both versions are compared.
+pub fn diff<K: Key, M: Key>(
+    old: &C3<K, M>,
+    new: &C3<K, M>,
+) -> Result<HierarchyDiff<K, M>, C3Error> {
+    let mut old_output = c3_linearization(old.clone())?;
+    old_output.copy_members(old);
+    let mut new_output = c3_linearization(new.clone())?;
+    new_output.copy_members(new);
+
+    let mut result = HierarchyDiff {
+        added: vec![],
+        removed: old
+            .all_classes()
+            .into_iter()
+            .filter(|class| !new.contains(class))
+            .collect(),
+        parents: vec![],
+        paths: vec![],
+        moved: vec![],
+    };
+    for class in new.all_classes() {
+        if !old.contains(&class) {
+            result.added.push(class);
+            continue;
+        }
+        if let Some(changed) = compare(&class, old.path(&class)?, new.path(&class)?) {
+            result.parents.push(changed);
+        }
+        let old_path = old_output.path(&class)?;
+        let new_path = new_output.path(&class)?;
+        if let Some(changed) = compare(&class, old_path, new_path) {
+            result.paths.push(changed);
+        }
+        for function in new_output.functions(&class)? {
+            let old_winner = old_output.resolve(&class, &function)?;
+            let new_winner = new_output.resolve(&class, &function)?;
+            if let (Some(old), Some(new)) = (old_winner, new_winner) {
+                if old != new {
+                    result.moved.push(Moved {
+                        class: class.clone(),
+                        function,
+                        old,
+                        new,
+                    });
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+fn compare<K: Key>(class: &K, old: Vec<K>, new: Vec<K>) -> Option<Changed<K>> {
+    if old == new {
+        None
+    } else {
+        Some(Changed {
+            class: class.clone(),
+            old,
+            new,
+        })
+    }
+}
+
+impl<K: Key, M: Key> Display for HierarchyDiff<K, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        for class in &self.added {
+            writeln!(f, "Added {}", class.label())?;
+        }
+        for class in &self.removed {
+            writeln!(f, "Removed {}", class.label())?;
+        }
+        for changed in &self.parents {
+            writeln!(f, "Parents of {}: {}", changed.class.label(), changed)?;
+        }
+        for changed in &self.paths {
+            writeln!(f, "MRO of {}: {}", changed.class.label(), changed)?;
+        }
+        for moved in &self.moved {
+            writeln!(
+                f,
+                "{} of {} moved from {} to {}",
+                moved.function.label(),
+                moved.class.label(),
+                moved.old.label(),
+                moved.new.label()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Displayed as `[old] -> [new]`.
+impl<K: Key> Display for Changed<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] -> [{}]", join(&self.old), join(&self.new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Class;
+
+    #[test]
+    fn test_diff() {
+        let mut old = C3::new();
+        old.add_class_str("Context", "").unwrap();
+        old.add_class_str("Ownable", "Context").unwrap();
+        old.add_class_str("Pausable", "Context").unwrap();
+        old.add_class_str("Legacy", "").unwrap();
+        old.add_class_str("Token", "Ownable, Pausable").unwrap();
+        old.register_fn_str("Ownable", "transfer").unwrap();
+        old.register_fn_str("Pausable", "transfer").unwrap();
+        old.register_fn_str("Context", "sender").unwrap();
+
+        let mut new = C3::new();
+        new.add_class_str("Context", "").unwrap();
+        new.add_class_str("Ownable", "Context").unwrap();
+        new.add_class_str("Pausable", "Context").unwrap();
+        new.add_class_str("Token", "Pausable, Ownable").unwrap();
+        new.add_class_str("Upgradeable", "Token").unwrap();
+        new.register_fn_str("Ownable", "transfer").unwrap();
+        new.register_fn_str("Pausable", "transfer").unwrap();
+        new.register_fn_str("Context", "sender").unwrap();
+
+        let result = diff(&old, &new).unwrap();
+        assert_eq!(result.added, vec![Class::from("Upgradeable")]);
+        assert_eq!(result.removed, vec![Class::from("Legacy")]);
+        assert_eq!(
+            result.moved,
+            vec![Moved {
+                class: Class::from("Token"),
+                function: Class::from("transfer"),
+                old: Class::from("Ownable"),
+                new: Class::from("Pausable"),
+            }]
+        );
+        assert_eq!(
+            result.to_string(),
+            "Added Upgradeable
+Removed Legacy
+Parents of Token: [Ownable, Pausable] -> [Pausable, Ownable]
+MRO of Token: [Token, Ownable, Pausable, Context] -> [Token, Pausable, Ownable, Context]
+transfer of Token moved from Ownable to Pausable
+"
+        );
+        assert!(diff(&new, &new).unwrap().is_empty());
+    }
+}
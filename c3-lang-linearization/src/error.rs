@@ -0,0 +1,212 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+use core::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum C3Error {
+    BaseClassDoesNotExists(String),
+    EmptySet,
+    PushingEmptySet,
+    NoMoreCandidates,
+    InconsistentMro(MroConflict),
+    Unresolved(Vec<Unresolved>),
+    Invalid(Vec<Invalid>),
+    HasDependents(String, Vec<String>),
+    Parse(ParseError),
+    Violated(Vec<Violation>),
+    InvalidId(String),
+}
+
+/// Details of a merge that ran out of candidates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MroConflict {
+    /// Class that was being linearized.
+    pub class: String,
+    /// Heads of the remaining sets. Every one of them is blocked by
+    /// the tail of another set, so their orders conflict.
+    pub conflicting: Vec<String>,
+    /// Sets left when the merge got stuck.
+    pub remaining: Vec<Vec<String>>,
+}
+
+/// Class that `c3_linearization` could not linearize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unresolved {
+    pub class: String,
+    pub reason: UnresolvedReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnresolvedReason {
+    /// The class is part of an inheritance cycle. The path starts and ends
+    /// with the class itself, e.g. `A, B, A`.
+    Cycle(Vec<String>),
+    /// A parent that is declared, but could not be linearized.
+    UnresolvedParent(String),
+}
+
+/// Problem found by `validate` in the input hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invalid {
+    pub class: String,
+    pub problem: Problem,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    EmptyName,
+    AlreadyDeclared,
+    EmptyParentName,
+    OwnParent,
+    DuplicatedParent(String),
+    UndeclaredParent(String),
+}
+
+/// Linearized class that breaks a guarantee checked by `verify`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub class: String,
+    pub rule: Rule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// The class has no path.
+    Missing,
+    /// The path doesn't start with the class or doesn't list
+    /// every ancestor exactly once.
+    Malformed,
+    /// Parents are declared in this order, but the path swaps them.
+    LocalPrecedence(String, String),
+    /// The path doesn't keep the order of the path of this parent.
+    Monotonicity(String),
+    /// The extended precedence graph requires this order, but the path swaps it.
+    ExtendedPrecedence(String, String),
+}
+
+/// Error in the textual hierarchy. Lines and columns start from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for C3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            C3Error::BaseClassDoesNotExists(class) => {
+                write!(f, "Base class {} does not exist", class)
+            }
+            C3Error::EmptySet => write!(f, "Empty set"),
+            C3Error::PushingEmptySet => write!(f, "Pushing an empty set"),
+            C3Error::NoMoreCandidates => write!(f, "No more candidates"),
+            C3Error::InconsistentMro(conflict) => conflict.fmt(f),
+            C3Error::Unresolved(classes) => {
+                let classes: Vec<String> = classes.iter().map(Unresolved::to_string).collect();
+                write!(f, "Cannot linearize classes: {}", classes.join("; "))
+            }
+            C3Error::Invalid(problems) => {
+                let problems: Vec<String> = problems.iter().map(Invalid::to_string).collect();
+                write!(f, "Invalid hierarchy: {}", problems.join("; "))
+            }
+            C3Error::HasDependents(class, dependents) => write!(
+                f,
+                "Class {} is required by {}",
+                class,
+                dependents.join(", ")
+            ),
+            C3Error::Parse(error) => write!(
+                f,
+                "Line {}, column {}: {}",
+                error.line, error.column, error.message
+            ),
+            C3Error::Violated(violations) => {
+                let violations: Vec<String> = violations.iter().map(Violation::to_string).collect();
+                write!(
+                    f,
+                    "Linearization breaks C3 guarantees: {}",
+                    violations.join("; ")
+                )
+            }
+            C3Error::InvalidId(name) => write!(f, "`{}` is not a valid identifier", name),
+        }
+    }
+}
+
+impl core::error::Error for C3Error {}
+
+impl Display for MroConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let remaining: Vec<String> = self
+            .remaining
+            .iter()
+            .map(|set| format!("[{}]", set.join(", ")))
+            .collect();
+        write!(
+            f,
+            "Cannot create a consistent method resolution order (MRO) for class {}: \
+             conflicting bases {}; remaining sets {}",
+            self.class,
+            self.conflicting.join(", "),
+            remaining.join(", ")
+        )
+    }
+}
+
+impl Display for Unresolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            UnresolvedReason::Cycle(path) => {
+                write!(f, "{} is part of cycle {}", self.class, path.join(" -> "))
+            }
+            UnresolvedReason::UnresolvedParent(parent) => {
+                write!(f, "{} depends on unresolved class {}", self.class, parent)
+            }
+        }
+    }
+}
+
+impl Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.problem {
+            Problem::EmptyName => write!(f, "a class has an empty name"),
+            Problem::AlreadyDeclared => write!(f, "{} is already declared", self.class),
+            Problem::EmptyParentName => write!(f, "{} has a parent with an empty name", self.class),
+            Problem::OwnParent => write!(f, "{} is its own parent", self.class),
+            Problem::DuplicatedParent(parent) => {
+                write!(f, "{} lists parent {} more than once", self.class, parent)
+            }
+            Problem::UndeclaredParent(parent) => {
+                write!(f, "{} has undeclared parent {}", self.class, parent)
+            }
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.rule {
+            Rule::Missing => write!(f, "{} has no path", self.class),
+            Rule::Malformed => write!(
+                f,
+                "path of {} must start with it and list each of its ancestors once",
+                self.class
+            ),
+            Rule::LocalPrecedence(first, second) => write!(
+                f,
+                "{} declares {} before {}, but its path puts {} after {}",
+                self.class, first, second, first, second
+            ),
+            Rule::Monotonicity(parent) => write!(
+                f,
+                "path of {} does not keep the order of the path of {}",
+                self.class, parent
+            ),
+            Rule::ExtendedPrecedence(first, second) => write!(
+                f,
+                "path of {} puts {} after {}, against the extended precedence graph",
+                self.class, first, second
+            ),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+use super::{C3Error, Key, C3};
+
+/// Queries over the declared hierarchy, where every class maps to its direct parents.
+impl<K: Key, M: Key> C3<K, M> {
+    /// Return direct parents in the declaration order.
+    pub fn parents(&self, class: &K) -> Result<Vec<K>, C3Error> {
+        self.path(class)
+    }
+
+    /// Return classes that list the class as a direct parent, in the declaration order.
+    pub fn children(&self, class: &K) -> Result<Vec<K>, C3Error> {
+        self.check_declared(class)?;
+        let children = self
+            .all_classes()
+            .into_iter()
+            .filter(|child| self.path(child).unwrap_or_default().contains(class))
+            .collect();
+        Ok(children)
+    }
+
+    /// Return all transitive parents, nearest first.
+    pub fn ancestors(&self, class: &K) -> Result<Vec<K>, C3Error> {
+        self.check_declared(class)?;
+        Ok(self.walk(class, |class| self.path(class).unwrap_or_default()))
+    }
+
+    /// Return all transitive children, nearest first.
+    pub fn descendants(&self, class: &K) -> Result<Vec<K>, C3Error> {
+        self.check_declared(class)?;
+        Ok(self.walk(class, |class| self.children(class).unwrap_or_default()))
+    }
+
+    /// Check if `class` is `base` or inherits from it.
+    pub fn is_subclass_of(&self, class: &K, base: &K) -> Result<bool, C3Error> {
+        self.check_declared(base)?;
+        Ok(class == base || self.ancestors(class)?.contains(base))
+    }
+
+    /// Return classes every given class is or inherits from,
+    /// in the order of ancestors of the first class.
+    pub fn common_ancestors(&self, classes: &[K]) -> Result<Vec<K>, C3Error> {
+        let mut lineages = vec![];
+        for class in classes {
+            let mut lineage = self.ancestors(class)?;
+            lineage.insert(0, class.clone());
+            lineages.push(lineage);
+        }
+        let (first, rest) = match lineages.split_first() {
+            Some(split) => split,
+            None => return Ok(vec![]),
+        };
+        let common = first
+            .iter()
+            .filter(|class| rest.iter().all(|lineage| lineage.contains(class)))
+            .cloned()
+            .collect();
+        Ok(common)
+    }
+
+    /// Return common ancestors that no other common ancestor inherits from.
+    pub fn lowest_common_ancestors(&self, classes: &[K]) -> Result<Vec<K>, C3Error> {
+        let common = self.common_ancestors(classes)?;
+        let mut lowest = vec![];
+        for candidate in &common {
+            let mut inherited = false;
+            for other in &common {
+                if other != candidate && self.is_subclass_of(other, candidate)? {
+                    inherited = true;
+                    break;
+                }
+            }
+            if !inherited {
+                lowest.push(candidate.clone());
+            }
+        }
+        Ok(lowest)
+    }
+
+    /// Compare positions of two classes in the path of a linearized `class`.
+    /// `Less` means `a` takes precedence over `b`.
+    /// `None` if any of them is not in the path.
+    pub fn precedence(&self, class: &K, a: &K, b: &K) -> Result<Option<Ordering>, C3Error> {
+        let path = self.path(class)?;
+        let position = |x: &K| path.iter().position(|class| class == x);
+        Ok(match (position(a), position(b)) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => None,
+        })
+    }
+
+    fn check_declared(&self, class: &K) -> Result<(), C3Error> {
+        if self.contains(class) {
+            Ok(())
+        } else {
+            Err(C3Error::BaseClassDoesNotExists(class.label()))
+        }
+    }
+
+    /// Breadth-first walk from `start`, excluding it.
+    /// Every class is visited once, so cycles are safe.
+    fn walk(&self, start: &K, next: impl Fn(&K) -> Vec<K>) -> Vec<K> {
+        let mut visited: BTreeSet<K> = BTreeSet::new();
+        visited.insert(start.clone());
+        let mut queue: VecDeque<K> = VecDeque::new();
+        queue.push_back(start.clone());
+        let mut result = vec![];
+        while let Some(class) = queue.pop_front() {
+            for class in next(&class) {
+                if visited.insert(class.clone()) {
+                    result.push(class.clone());
+                    queue.push_back(class);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use crate::{c3_linearization, fixtures::classes, C3Error, Class, C3};
+
+    fn input() -> C3 {
+        let mut input = C3::new();
+        input.add_class_str("O", "").unwrap();
+        input.add_class_str("A", "O").unwrap();
+        input.add_class_str("B", "O").unwrap();
+        input.add_class_str("C", "O").unwrap();
+        input.add_class_str("K1", "A, B").unwrap();
+        input.add_class_str("K2", "B, C").unwrap();
+        input.add_class_str("Z", "K1, K2").unwrap();
+        input
+    }
+
+    #[test]
+    fn test_navigation() {
+        let input = input();
+        let class = |name: &str| Class::from(name);
+
+        assert_eq!(input.parents(&class("K1")), Ok(classes(&["A", "B"])));
+        assert_eq!(input.children(&class("B")), Ok(classes(&["K1", "K2"])));
+        assert_eq!(
+            input.ancestors(&class("Z")),
+            Ok(classes(&["K1", "K2", "A", "B", "C", "O"]))
+        );
+        assert_eq!(input.descendants(&class("A")), Ok(classes(&["K1", "Z"])));
+        assert_eq!(input.is_subclass_of(&class("Z"), &class("C")), Ok(true));
+        assert_eq!(input.is_subclass_of(&class("K1"), &class("C")), Ok(false));
+        assert_eq!(input.is_subclass_of(&class("A"), &class("A")), Ok(true));
+        assert_eq!(
+            input.children(&class("X")),
+            Err(C3Error::BaseClassDoesNotExists(String::from("X")))
+        );
+    }
+
+    #[test]
+    fn test_common_ancestors() {
+        let input = input();
+        assert_eq!(
+            input.common_ancestors(&classes(&["K1", "K2"])),
+            Ok(classes(&["B", "O"]))
+        );
+        assert_eq!(
+            input.lowest_common_ancestors(&classes(&["K1", "K2"])),
+            Ok(classes(&["B"]))
+        );
+        assert_eq!(
+            input.lowest_common_ancestors(&classes(&["Z", "K2", "C"])),
+            Ok(classes(&["C"]))
+        );
+        assert_eq!(
+            input.lowest_common_ancestors(&classes(&["A", "C"])),
+            Ok(classes(&["O"]))
+        );
+        assert_eq!(input.common_ancestors(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_precedence() {
+        let output = c3_linearization(input()).unwrap();
+        let class = |name: &str| Class::from(name);
+        assert_eq!(
+            output.precedence(&class("Z"), &class("A"), &class("C")),
+            Ok(Some(Ordering::Less))
+        );
+        assert_eq!(
+            output.precedence(&class("Z"), &class("O"), &class("K2")),
+            Ok(Some(Ordering::Greater))
+        );
+        assert_eq!(
+            output.precedence(&class("K1"), &class("A"), &class("C")),
+            Ok(None)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use super::{c3_linearization, C3Error, Invalid, Key, Problem, C3};
+
+/// Directed acyclic graph that can be linearized.
+pub trait Hierarchy {
+    type Node: Key;
+
+    /// All nodes of the graph. Every node must be listed once.
+    fn nodes(&self) -> Vec<Self::Node>;
+
+    /// Direct parents of the node in the precedence order.
+    fn parents(&self, node: &Self::Node) -> Vec<Self::Node>;
+}
+
+/// Linearize every node of the graph.
+/// The result maps every node to its path, starting with the node itself.
+pub fn linearize<G: Hierarchy>(graph: &G) -> Result<C3<G::Node>, C3Error> {
+    let mut input = C3::new();
+    let mut problems = vec![];
+    for node in graph.nodes() {
+        if input.contains(&node) {
+            problems.push(Invalid {
+                class: node.label(),
+                problem: Problem::AlreadyDeclared,
+            });
+            continue;
+        }
+        let parents = graph.parents(&node);
+        input.add(node, parents);
+    }
+    if !problems.is_empty() {
+        return Err(C3Error::Invalid(problems));
+    }
+    c3_linearization(input)
+}
+
+/// Classes declared with their direct parents.
+impl<K: Key, M: Key> Hierarchy for C3<K, M> {
+    type Node = K;
+
+    fn nodes(&self) -> Vec<K> {
+        self.all_classes()
+    }
+
+    fn parents(&self, node: &K) -> Vec<K> {
+        self.path(node).unwrap_or_default()
+    }
+}
+
+/// Nodes mapped to their direct parents.
+impl<K: Key> Hierarchy for BTreeMap<K, Vec<K>> {
+    type Node = K;
+
+    fn nodes(&self) -> Vec<K> {
+        self.keys().cloned().collect()
+    }
+
+    fn parents(&self, node: &K) -> Vec<K> {
+        self.get(node).cloned().unwrap_or_default()
+    }
+}
+
+/// Nodes are identified by their indexes, so weights don't have to be unique.
+/// Edges point from a child to its parent. Parents are ordered by the insertion of edges.
+#[cfg(feature = "petgraph")]
+impl<N, E, Ix: petgraph::graph::IndexType> Hierarchy
+    for petgraph::Graph<N, E, petgraph::Directed, Ix>
+{
+    type Node = petgraph::graph::NodeIndex<Ix>;
+
+    fn nodes(&self) -> Vec<Self::Node> {
+        self.node_indices().collect()
+    }
+
+    fn parents(&self, node: &Self::Node) -> Vec<Self::Node> {
+        use petgraph::visit::EdgeRef;
+
+        let mut edges: Vec<_> = self
+            .edges_directed(*node, petgraph::Outgoing)
+            .map(|edge| (edge.id(), edge.target()))
+            .collect();
+        edges.sort();
+        edges.into_iter().map(|(_, parent)| parent).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linearize_map() {
+        let mut plugins: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        plugins.insert("core", vec![]);
+        plugins.insert("log", vec!["core"]);
+        plugins.insert("metrics", vec!["core"]);
+        plugins.insert("app", vec!["metrics", "log"]);
+
+        let output = linearize(&plugins).unwrap();
+        assert_eq!(
+            output.path(&"app"),
+            Ok(vec!["app", "metrics", "log", "core"])
+        );
+
+        plugins.insert("core", vec!["app"]);
+        assert!(matches!(linearize(&plugins), Err(C3Error::Unresolved(_))));
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn test_linearize_petgraph() {
+        let mut graph: petgraph::Graph<&str, ()> = petgraph::Graph::new();
+        let a = graph.add_node("A");
+        let b = graph.add_node("B");
+        let c = graph.add_node("C");
+        let d = graph.add_node("D");
+        graph.add_edge(b, a, ());
+        graph.add_edge(c, a, ());
+        graph.add_edge(d, b, ());
+        graph.add_edge(d, c, ());
+
+        let output = linearize(&graph).unwrap();
+        assert_eq!(output.path(&d), Ok(vec![d, b, c, a]));
+
+        let other = graph.add_node("A");
+        graph.add_edge(other, d, ());
+        let output = linearize(&graph).unwrap();
+        assert_eq!(output.path(&other), Ok(vec![other, d, b, c, a]));
+
+        graph.add_edge(a, other, ());
+        assert!(matches!(linearize(&graph), Err(C3Error::Unresolved(_))));
+    }
+}
@@ -1,39 +1,253 @@
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use super::{is_identifier, C3Error};
+
+#[cfg(feature = "quote")]
 use quote::{format_ident, quote, ToTokens};
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// Keywords that are emitted as raw identifiers, e.g. `r#type`.
+#[cfg(feature = "quote")]
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords allowed only at the start of the module path. They can't be raw identifiers.
+const PATH_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Name of a class, function or variable, optionally qualified with a module path,
+/// e.g. `access::Ownable`. Ids compare by the module path first, then by the name.
+/// `Id::new` and `FromStr` validate the name, `From` conversions don't.
+/// `Debug` prints the same text as `Display`, which labels the id in errors.
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(into = "String"))]
 pub struct Id {
+    path: Vec<String>,
     name: String,
 }
 
+impl Id {
+    /// Build an id from a possibly qualified name, e.g. `access::Ownable`.
+    /// Fails if any segment is not a valid identifier.
+    /// Keywords can be written plain or raw, e.g. `type` or `r#type`.
+    pub fn new(name: &str) -> Result<Self, C3Error> {
+        let segments: Vec<&str> = name.split("::").collect();
+        let (last, path) = segments.split_last().unwrap();
+        let mut previous = None;
+        for segment in path {
+            if !is_path_keyword(segment, previous) && !is_segment(segment) {
+                return Err(C3Error::InvalidId(String::from(name)));
+            }
+            previous = Some(*segment);
+        }
+        if is_segment(last) {
+            Ok(Id::from(name))
+        } else {
+            Err(C3Error::InvalidId(String::from(name)))
+        }
+    }
+
+    /// Build an id of a name declared in the given module path.
+    /// Validated the same way as `Id::new`.
+    pub fn qualified(path: &[&str], name: &str) -> Result<Self, C3Error> {
+        let mut segments = path.to_vec();
+        segments.push(name);
+        Id::new(&segments.join("::"))
+    }
+
+    /// Name without the module path.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Module path, empty for unqualified ids.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub fn is_qualified(&self) -> bool {
+        !self.path.is_empty()
+    }
+}
+
+/// Check if the segment is an identifier, a keyword or a raw identifier.
+fn is_segment(segment: &str) -> bool {
+    match segment.strip_prefix("r#") {
+        Some(raw) => is_identifier(raw) && !PATH_KEYWORDS.contains(&raw),
+        None => is_identifier(segment) && !PATH_KEYWORDS.contains(&segment),
+    }
+}
+
+/// `crate`, `self` and `Self` can only start the path, `super` can follow `self` or `super`.
+fn is_path_keyword(segment: &str, previous: Option<&str>) -> bool {
+    match segment {
+        "crate" | "self" | "Self" => previous.is_none(),
+        "super" => matches!(previous, None | Some("self") | Some("super")),
+        _ => false,
+    }
+}
+
+/// Split the name on `::`. The last segment is the name, the rest is the module path.
+/// Raw identifiers are stored without the `r#` prefix.
+/// Unchecked: the name is not validated, use `Id::new` for untrusted input.
 impl From<&str> for Id {
     fn from(name: &str) -> Self {
-        Id {
-            name: String::from(name),
-        }
+        let mut path: Vec<String> = name
+            .split("::")
+            .map(|segment| String::from(segment.strip_prefix("r#").unwrap_or(segment)))
+            .collect();
+        let name = path.pop().unwrap_or_default();
+        Id { path, name }
     }
 }
 
+/// Unchecked, same as `From<&str>`.
 impl From<String> for Id {
     fn from(name: String) -> Self {
-        Id { name }
+        if name.contains("::") || name.starts_with("r#") {
+            Id::from(name.as_str())
+        } else {
+            Id {
+                path: Vec::new(),
+                name,
+            }
+        }
+    }
+}
+
+impl FromStr for Id {
+    type Err = C3Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Id::new(name)
+    }
+}
+
+/// Deserialized from a string validated with `Id::new`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Id {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Id::new(&name).map_err(serde::de::Error::custom)
     }
 }
 
 impl From<Id> for String {
     fn from(id: Id) -> Self {
-        id.name
+        if id.path.is_empty() {
+            id.name
+        } else {
+            format!("{}", id)
+        }
     }
 }
 
-impl ToString for Id {
-    fn to_string(&self) -> String {
-        self.name.clone()
+impl fmt::Debug for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
     }
 }
 
+impl Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.path {
+            write!(f, "{}::", segment)?;
+        }
+        f.write_str(&self.name)
+    }
+}
+
+/// Keywords are emitted as raw identifiers.
+/// Ids that were not validated and are not valid paths emit `compile_error!`.
+#[cfg(feature = "quote")]
 impl ToTokens for Id {
     fn to_tokens(&self, tokens: &mut quote::__private::TokenStream) {
-        let name = format_ident!("{}", &self.name);
-        tokens.extend(quote! {#name});
+        let text = format!("{}", self);
+        if Id::new(&text).is_err() {
+            let message = format!("`{}` is not a valid identifier", text);
+            tokens.extend(quote! {compile_error!(#message)});
+            return;
+        }
+        let segments = self.path.iter().chain(Some(&self.name)).map(|segment| {
+            if KEYWORDS.contains(&segment.as_str()) {
+                format_ident!("r#{}", segment)
+            } else {
+                format_ident!("{}", segment)
+            }
+        });
+        tokens.extend(quote! {#(#segments)::*});
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qualified() {
+        let access = Id::from("access::Ownable");
+        let legacy = Id::qualified(&["legacy"], "Ownable").unwrap();
+        assert_eq!(access, Id::qualified(&["access"], "Ownable").unwrap());
+        assert_ne!(access, legacy);
+        assert_ne!(access, Id::from("Ownable"));
+        assert!(access < legacy);
+        assert!(Id::from("Ownable") < access);
+
+        assert_eq!(access.name(), "Ownable");
+        assert_eq!(access.path(), &[String::from("access")]);
+        assert!(!Id::from("Ownable").is_qualified());
+        assert_eq!(legacy.to_string(), "legacy::Ownable");
+        assert_eq!(String::from(legacy), "legacy::Ownable");
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(Id::new("r#type"), Ok(Id::from("type")));
+        assert_eq!("type".parse(), Ok(Id::from("r#type")));
+        assert!(Id::new("crate::access::Ownable").is_ok());
+        assert!(Id::new("self::super::Ownable").is_ok());
+        assert_eq!(
+            Id::new("ERC-20"),
+            Err(C3Error::InvalidId(String::from("ERC-20")))
+        );
+        assert!(Id::new("").is_err());
+        assert!(Id::new("access::").is_err());
+        assert!(Id::new("access::crate::Ownable").is_err());
+        assert!(Id::new("crate::super::Ownable").is_err());
+        assert!(Id::new("self").is_err());
+        assert!(Id::new("r#crate::Ownable").is_err());
+        assert!(Id::qualified(&["access"], "r#").is_err());
+        assert!(Id::new("A²").is_err());
+    }
+
+    #[cfg(feature = "quote")]
+    #[test]
+    fn test_to_tokens() {
+        let id = Id::from("crate::access::Ownable");
+        assert_eq!(
+            quote! {#id}.to_string(),
+            quote! {crate::access::Ownable}.to_string()
+        );
+        let id = Id::new("mod::type").unwrap();
+        assert_eq!(quote! {#id}.to_string(), quote! {r#mod::r#type}.to_string());
+        let id = Id::from("ERC-20");
+        assert_eq!(
+            quote! {#id}.to_string(),
+            quote! {compile_error!("`ERC-20` is not a valid identifier")}.to_string()
+        );
+        let id = Id::from("A²");
+        assert_eq!(
+            quote! {#id}.to_string(),
+            quote! {compile_error!("`A²` is not a valid identifier")}.to_string()
+        );
+        let id = Id::new("Żółw").unwrap();
+        assert_eq!(quote! {#id}.to_string(), "Żółw");
     }
 }
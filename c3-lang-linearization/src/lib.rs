@@ -1,38 +1,101 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
 mod c3;
 mod c3_linearization;
+mod diamond;
+mod diff;
+mod error;
+mod graph;
+mod hierarchy;
 mod id;
+mod ordered_map;
+mod parse;
+mod render;
 mod sets;
+mod strategy;
+mod suggest;
+mod trace;
+mod validation;
+mod verify;
 
-pub use crate::c3::{Class, Fn, Var, C3};
-pub use c3_linearization::c3_linearization;
+pub use crate::c3::{Class, Fn, Key, Member, Var, C3};
+pub use c3_linearization::{c3_linearization, linearization_with};
+pub use diamond::{diamonds, Diamond};
+pub use diff::{diff, Changed, HierarchyDiff, Moved};
+pub use error::{
+    C3Error, Invalid, MroConflict, ParseError, Problem, Rule, Unresolved, UnresolvedReason,
+    Violation,
+};
+pub use hierarchy::{linearize, Hierarchy};
+pub use parse::{is_identifier, parse_class, parse_parents};
 use sets::Sets;
+pub use strategy::{C3Merge, DepthFirst, Dylan, LinearizationStrategy, Solidity};
+pub use suggest::{suggest_fix, Change, Fix};
+pub use trace::{explain, merge_trace, MergeStep, MergeTrace, Rejection};
+pub use validation::validate;
+pub use verify::verify;
 
-#[derive(Debug)]
-pub enum C3Error {
-    BaseClassDoesNotExists(String),
-    EmptySet,
-    PushingEmptySet,
-    NoMoreCandidates,
-}
-
-/// Parse coma separated list of parents.
-/// TODO: Implement using regexp
+/// Split coma separated list of parents. Whitespaces around names are ignored.
+/// Use `parse_parents` to validate the names.
 pub fn split_coma(s: &str) -> Vec<String> {
-    s.split(", ")
-        .map(String::from)
+    s.split(',')
+        .map(str::trim)
         .filter(|x| !x.is_empty())
+        .map(String::from)
         .collect()
 }
 
-/// Check if element is in the tail of the list.
-fn in_tail<T: Eq>(element: &T, list: &[T]) -> Result<bool, C3Error> {
-    match list.split_first() {
-        Some((_, tail)) => Ok(tail.contains(element)),
-        None => Err(C3Error::EmptySet),
-    }
-}
-
 /// Check if is `smaller` is subset of `larger`.
 pub fn is_subset<T: Eq>(larger: &[T], smaller: &[T]) -> bool {
     smaller.iter().all(|item| larger.contains(item))
 }
+
+/// Join labels of the keys with comas.
+pub(crate) fn join<K: Key>(list: &[K]) -> String {
+    list.iter()
+        .map(K::label)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Hierarchies and helpers shared by tests.
+#[cfg(test)]
+mod fixtures {
+    use crate::{Class, C3};
+
+    pub fn classes(names: &[&str]) -> Vec<Class> {
+        names.iter().map(|name| Class::from(*name)).collect()
+    }
+
+    /// `D` inherits from `B` and `C`, both inheriting from `A`.
+    pub fn diamond() -> C3 {
+        let mut input = C3::new();
+        input.add_class_str("A", "").unwrap();
+        input.add_class_str("B", "A").unwrap();
+        input.add_class_str("C", "A").unwrap();
+        input.add_class_str("D", "B, C").unwrap();
+        input
+    }
+
+    /// Hierarchy from the C3 paper, where C3 and Dylan linearizations differ.
+    pub fn panes() -> C3 {
+        let mut input = C3::new();
+        input.add_class_str("Pane", "").unwrap();
+        input.add_class_str("ScrollingMixin", "").unwrap();
+        input.add_class_str("EditingMixin", "").unwrap();
+        input
+            .add_class_str("ScrollablePane", "Pane, ScrollingMixin")
+            .unwrap();
+        input
+            .add_class_str("EditablePane", "Pane, EditingMixin")
+            .unwrap();
+        input
+            .add_class_str("EditableScrollablePane", "ScrollablePane, EditablePane")
+            .unwrap();
+        input
+    }
+}
@@ -0,0 +1,146 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Map that iterates over keys in the insertion order.
+/// Two maps are equal if they hold the same entries, regardless of the order.
+#[derive(Debug, Clone)]
+pub struct OrderedMap<K, V> {
+    keys: Vec<K>,
+    values: BTreeMap<K, V>,
+}
+
+impl<K: Clone + Ord, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            keys: Vec::new(),
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Insert a value. Replacing a value keeps the original position of the key.
+    pub fn insert(&mut self, key: K, value: V) {
+        if !self.values.contains_key(&key) {
+            self.keys.push(key.clone());
+        }
+        self.values.insert(key, value);
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    /// Return value for the key, inserting the default one if it's missing.
+    pub fn get_or_default(&mut self, key: K) -> &mut V
+    where
+        V: Default,
+    {
+        if !self.values.contains_key(&key) {
+            self.keys.push(key.clone());
+        }
+        self.values.entry(key).or_default()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.values.remove(key)?;
+        self.keys.retain(|k| k != key);
+        Some(value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.keys.iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.keys.iter().map(move |key| (key, &self.values[key]))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<K: Clone + Ord, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V: PartialEq> PartialEq for OrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<K: Ord, V: Eq> Eq for OrderedMap<K, V> {}
+
+#[cfg(feature = "serde")]
+impl<K: Clone + Ord + serde::Serialize, V: serde::Serialize> serde::Serialize for OrderedMap<K, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for OrderedMap<K, V>
+where
+    K: Clone + Ord + serde::Deserialize<'de>,
+    V: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor<K, V>(core::marker::PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for MapVisitor<K, V>
+        where
+            K: Clone + Ord + serde::Deserialize<'de>,
+            V: serde::Deserialize<'de>,
+        {
+            type Value = OrderedMap<K, V>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut map = OrderedMap::new();
+                while let Some((key, value)) = access.next_entry()? {
+                    map.insert(key, value);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(core::marker::PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderedMap;
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map = OrderedMap::new();
+        map.insert("b", 1);
+        map.insert("a", 2);
+        map.insert("c", 3);
+        map.insert("b", 4);
+        *map.get_or_default("d") += 5;
+        assert_eq!(map.remove(&"c"), Some(3));
+
+        let entries: Vec<(&str, i32)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(entries, vec![("b", 4), ("a", 2), ("d", 5)]);
+
+        let mut other = OrderedMap::new();
+        other.insert("d", 5);
+        other.insert("a", 2);
+        other.insert("b", 4);
+        assert_eq!(map, other);
+    }
+}
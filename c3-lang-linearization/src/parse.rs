@@ -0,0 +1,263 @@
+use alloc::{format, string::String, vec, vec::Vec};
+use core::str::FromStr;
+use unicode_ident::{is_xid_continue, is_xid_start};
+
+use super::{C3Error, Class, ParseError, C3};
+
+/// Parse hierarchy in the text format, one class per line:
+///
+/// ```text
+/// # Comments start with a hash.
+/// Context:
+/// ERC20: Context, IERC20  # Parents are separated with comas.
+/// access::Ownable: Context  # Names can be qualified with a module path.
+/// ```
+impl FromStr for C3 {
+    type Err = C3Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut c3 = C3::new();
+        for (index, line) in text.lines().enumerate() {
+            let mut cursor = Cursor::new(index + 1, line);
+            cursor.skip_whitespace();
+            if cursor.is_end() {
+                continue;
+            }
+            let column = cursor.column();
+            let class = cursor.class()?;
+            if c3.contains(&class) {
+                return Err(cursor.error_at(column, format!("class {} is already declared", class)));
+            }
+            cursor.skip_whitespace();
+            cursor.expect(':')?;
+            let parents = cursor.parents()?;
+            c3.add(class, parents);
+        }
+        Ok(c3)
+    }
+}
+
+/// Parse a single, possibly qualified, class name.
+pub fn parse_class(text: &str) -> Result<Class, C3Error> {
+    let mut cursor = Cursor::new(1, text);
+    cursor.skip_whitespace();
+    let class = cursor.class()?;
+    cursor.skip_whitespace();
+    if !cursor.is_end() {
+        return Err(cursor.error(String::from("expected end of class name")));
+    }
+    Ok(class)
+}
+
+/// Parse coma separated list of parents.
+pub fn parse_parents(text: &str) -> Result<Vec<Class>, C3Error> {
+    Cursor::new(1, text).parents()
+}
+
+/// Check if the name is a valid identifier, following the Rust `XID_Start`/`XID_Continue` rules.
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if is_xid_start(first) || first == '_' => {
+            name != "_" && chars.all(is_xid_continue)
+        }
+        _ => false,
+    }
+}
+
+struct Cursor {
+    line: usize,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Cursor {
+    /// Build a cursor over a single line, without the comment.
+    /// `#` of a raw identifier, like `r#type`, doesn't start a comment.
+    fn new(line: usize, text: &str) -> Self {
+        let mut chars: Vec<char> = text.chars().collect();
+        let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+        let comment = (0..chars.len()).find(|index| {
+            chars[*index] == '#'
+                && !(*index > 0
+                    && chars[index - 1] == 'r'
+                    && (*index == 1 || !is_word(&chars[index - 2])))
+        });
+        if let Some(comment) = comment {
+            chars.truncate(comment);
+        }
+        Cursor {
+            line,
+            chars,
+            position: 0,
+        }
+    }
+
+    fn is_end(&self) -> bool {
+        self.position >= self.chars.len()
+    }
+
+    fn column(&self) -> usize {
+        self.position + 1
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), C3Error> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("expected `{}`", expected))),
+        }
+    }
+
+    /// Parse a list of parents up to the end of the line.
+    fn parents(&mut self) -> Result<Vec<Class>, C3Error> {
+        let mut parents: Vec<Class> = vec![];
+        self.skip_whitespace();
+        while !self.is_end() {
+            if !parents.is_empty() {
+                self.expect(',')?;
+                self.skip_whitespace();
+            }
+            parents.push(self.class()?);
+            self.skip_whitespace();
+        }
+        Ok(parents)
+    }
+
+    /// Parse a single class name. A single `:` ends the name, `::` separates path segments.
+    fn class(&mut self) -> Result<Class, C3Error> {
+        let column = self.column();
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c == ':' && self.chars.get(self.position + 1) == Some(&':') {
+                name.push_str("::");
+                self.position += 2;
+                continue;
+            }
+            if c.is_whitespace() || c == ':' || c == ',' {
+                break;
+            }
+            name.push(c);
+            self.position += 1;
+        }
+        if name.is_empty() {
+            return Err(self.error(String::from("expected class name")));
+        }
+        Class::new(&name)
+            .map_err(|_| self.error_at(column, format!("`{}` is not a valid class name", name)))
+    }
+
+    fn error(&self, message: String) -> C3Error {
+        self.error_at(self.column(), message)
+    }
+
+    fn error_at(&self, column: usize, message: String) -> C3Error {
+        C3Error::Parse(ParseError {
+            line: self.line,
+            column,
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> String {
+        text.parse::<C3>().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "
+            # Diamond.
+            A:
+            B :A
+            C: A   # Comment.
+            r#type: A#Comment.
+            D:B,C
+        ";
+        let c3: C3 = text.parse().unwrap();
+        let mut target = C3::new();
+        target.add_class_str("A", "").unwrap();
+        target.add_class_str("B", "A").unwrap();
+        target.add_class_str("C", "A").unwrap();
+        target.add_class_str("D", "B, C").unwrap();
+        target.add_class_str("type", "A").unwrap();
+        assert_eq!(c3, target);
+    }
+
+    #[test]
+    fn test_parse_qualified() {
+        let c3: C3 = "access::Ownable:\nlegacy::Ownable:\nToken: access::Ownable"
+            .parse()
+            .unwrap();
+        assert_eq!(c3.all_classes().len(), 3);
+        assert_eq!(
+            c3.path(&Class::from("Token")),
+            Ok(vec![Class::qualified(&["access"], "Ownable").unwrap()])
+        );
+        assert_eq!(
+            parse_class("crate::r#type"),
+            Class::qualified(&["crate"], "type")
+        );
+        assert_eq!(
+            error("access::: A"),
+            "Line 1, column 1: `access::` is not a valid class name"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            error("A:\nB A"),
+            "Line 2, column 3: expected `:`, found `A`"
+        );
+        assert_eq!(error("A: B C"), "Line 1, column 6: expected `,`, found `C`");
+        assert_eq!(error("A: B,"), "Line 1, column 6: expected class name");
+        assert_eq!(
+            error("  ERC-20: A"),
+            "Line 1, column 3: `ERC-20` is not a valid class name"
+        );
+        assert_eq!(
+            error("A:\nA: B"),
+            "Line 2, column 1: class A is already declared"
+        );
+    }
+
+    #[test]
+    fn test_parse_parents() {
+        let parents = parse_parents(" A,B , C ").unwrap();
+        assert_eq!(
+            parents,
+            vec![Class::from("A"), Class::from("B"), Class::from("C")]
+        );
+        assert!(parse_parents("A B").is_err());
+    }
+
+    #[test]
+    fn test_is_identifier() {
+        assert!(is_identifier("ERC20"));
+        assert!(is_identifier("_private"));
+        assert!(!is_identifier("_"));
+        assert!(!is_identifier("20ERC"));
+        assert!(!is_identifier("ERC-20"));
+        assert!(is_identifier("Żółw"));
+        assert!(!is_identifier("A²"));
+        assert!(!is_identifier(""));
+    }
+}
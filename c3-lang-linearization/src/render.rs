@@ -0,0 +1,138 @@
+use super::{c3_linearization, join, C3Error, Key, C3};
+use alloc::{format, string::String};
+
+impl<K: Key, M: Key> C3<K, M> {
+    /// Render the declared hierarchy as a Graphviz DOT graph.
+    /// Every node shows its path, edges are numbered in the local precedence order.
+    pub fn to_dot(&self) -> Result<String, C3Error> {
+        let output = c3_linearization(self.clone())?;
+        let mut dot = String::from("digraph C3 {\n    rankdir=BT;\n    node [shape=box];\n");
+        for class in self.all_classes() {
+            let name = dot_escape(&class.label());
+            let path = dot_escape(&join(&output.path(&class)?));
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\nMRO: {}\"];\n",
+                name, name, path
+            ));
+        }
+        for class in self.all_classes() {
+            for (index, parent) in self.path(&class)?.iter().enumerate() {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                    dot_escape(&class.label()),
+                    dot_escape(&parent.label()),
+                    index + 1
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        Ok(dot)
+    }
+
+    /// Render the declared hierarchy as a Mermaid class diagram.
+    /// Every class shows its path, edges are numbered in the local precedence order.
+    pub fn to_mermaid(&self) -> Result<String, C3Error> {
+        let output = c3_linearization(self.clone())?;
+        let mut mermaid = String::from("classDiagram\n");
+        for class in self.all_classes() {
+            mermaid.push_str(&format!(
+                "    class {} {{\n        MRO: {}\n    }}\n",
+                mermaid_name(&class.label()),
+                join(&output.path(&class)?)
+            ));
+        }
+        for class in self.all_classes() {
+            for (index, parent) in self.path(&class)?.iter().enumerate() {
+                mermaid.push_str(&format!(
+                    "    {} <|-- {} : {}\n",
+                    mermaid_name(&parent.label()),
+                    mermaid_name(&class.label()),
+                    index + 1
+                ));
+            }
+        }
+        Ok(mermaid)
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Names other than plain identifiers, e.g. `access::Ownable`, are wrapped in backticks.
+fn mermaid_name(text: &str) -> String {
+    if !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        String::from(text)
+    } else {
+        format!("`{}`", text.replace('`', "'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixtures::diamond, C3};
+
+    #[test]
+    fn test_dot() {
+        assert_eq!(
+            diamond().to_dot().unwrap(),
+            r#"digraph C3 {
+    rankdir=BT;
+    node [shape=box];
+    "A" [label="A\nMRO: A"];
+    "B" [label="B\nMRO: B, A"];
+    "C" [label="C\nMRO: C, A"];
+    "D" [label="D\nMRO: D, B, C, A"];
+    "B" -> "A" [label="1"];
+    "C" -> "A" [label="1"];
+    "D" -> "B" [label="1"];
+    "D" -> "C" [label="2"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_mermaid() {
+        assert_eq!(
+            diamond().to_mermaid().unwrap(),
+            "classDiagram
+    class A {
+        MRO: A
+    }
+    class B {
+        MRO: B, A
+    }
+    class C {
+        MRO: C, A
+    }
+    class D {
+        MRO: D, B, C, A
+    }
+    A <|-- B : 1
+    A <|-- C : 1
+    B <|-- D : 1
+    C <|-- D : 2
+"
+        );
+    }
+
+    #[test]
+    fn test_mermaid_qualified() {
+        let mut input = C3::new();
+        input.add_class_str("access::Ownable", "").unwrap();
+        input.add_class_str("Token", "access::Ownable").unwrap();
+        assert_eq!(
+            input.to_mermaid().unwrap(),
+            "classDiagram
+    class `access::Ownable` {
+        MRO: access::Ownable
+    }
+    class Token {
+        MRO: Token, access::Ownable
+    }
+    `access::Ownable` <|-- Token : 1
+"
+        );
+    }
+}
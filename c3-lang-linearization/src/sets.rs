@@ -1,59 +1,154 @@
-use super::{in_tail, C3Error};
+use alloc::collections::BTreeMap;
+use alloc::{string::String, vec, vec::Vec};
 
-// TODO: Comment and test.
+use super::{C3Error, Key, MroConflict};
+
+/// Sets of classes merged by the C3 algorithm.
+///
+/// Every set keeps a cursor to its current head, so picking a solution never
+/// shifts the underlying vectors. `tails` counts how many times each element
+/// occurs in the tails of all sets, so checking a candidate is a single lookup.
 pub struct Sets<T> {
     sets: Vec<Vec<T>>,
+    heads: Vec<usize>,
+    tails: BTreeMap<T, usize>,
+    remaining: usize,
 }
 
-impl<T: Clone + Eq + PartialEq> Sets<T> {
+impl<T: Clone + Ord> Sets<T> {
     pub fn new() -> Self {
-        Self { sets: Vec::new() }
+        Self {
+            sets: Vec::new(),
+            heads: Vec::new(),
+            tails: BTreeMap::new(),
+            remaining: 0,
+        }
     }
 
     pub fn push(&mut self, set: Vec<T>) -> Result<(), C3Error> {
         if set.is_empty() {
-            Err(C3Error::PushingEmptySet)
-        } else {
-            self.sets.push(set);
-            Ok(())
+            return Err(C3Error::PushingEmptySet);
         }
+        for element in &set[1..] {
+            *self.tails.entry(element.clone()).or_insert(0) += 1;
+        }
+        self.sets.push(set);
+        self.heads.push(0);
+        self.remaining += 1;
+        Ok(())
     }
 
     pub fn is_empty(&self) -> bool {
-        self.sets.is_empty()
+        self.remaining == 0
     }
 
+    /// Pick the first head that is not in the tail of any set
+    /// and remove it from all sets.
     pub fn find_solution(&mut self) -> Result<T, C3Error> {
-        for candidate in self.candidates() {
-            if self.is_solution(&candidate)? {
-                self.remove_solution(&candidate);
-                return Ok(candidate);
+        let solution = self
+            .candidates()
+            .find(|candidate| !self.tails.contains_key(*candidate))
+            .cloned();
+        match solution {
+            Some(solution) => {
+                self.remove_solution(&solution);
+                Ok(solution)
             }
+            None => Err(C3Error::NoMoreCandidates),
         }
-        Err(C3Error::NoMoreCandidates)
     }
 
-    fn candidates(&self) -> Vec<T> {
+    /// Check if the element is a head of some set and is not in any tail.
+    pub fn is_candidate(&self, element: &T) -> bool {
+        !self.tails.contains_key(element) && self.candidates().any(|head| head == element)
+    }
+
+    /// Heads of all not yet exhausted sets.
+    fn candidates(&self) -> impl Iterator<Item = &T> {
         self.sets
             .iter()
-            .map(|set| set.first().unwrap())
-            .cloned()
-            .collect()
+            .zip(&self.heads)
+            .filter_map(|(set, head)| set.get(*head))
     }
 
-    fn is_solution(&self, candidate: &T) -> Result<bool, C3Error> {
-        for set in &self.sets {
-            if in_tail(candidate, set)? {
-                return Ok(false);
+    /// Advance every set that starts with the solution.
+    /// A solution is never in a tail, so it's enough to check the heads.
+    pub fn remove_solution(&mut self, solution: &T) {
+        for (set, head) in self.sets.iter().zip(self.heads.iter_mut()) {
+            if set.get(*head) != Some(solution) {
+                continue;
+            }
+            *head += 1;
+            match set.get(*head) {
+                Some(next) => {
+                    let count = self.tails.get_mut(next).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        self.tails.remove(next);
+                    }
+                }
+                None => self.remaining -= 1,
             }
         }
-        Ok(true)
     }
 
-    fn remove_solution(&mut self, solution: &T) {
-        for set in self.sets.iter_mut() {
-            set.retain(|x| x != solution);
+    /// Not yet merged part of every set, including exhausted ones.
+    pub fn snapshot(&self) -> Vec<Vec<T>> {
+        self.sets
+            .iter()
+            .zip(&self.heads)
+            .map(|(set, head)| set[*head..].to_vec())
+            .collect()
+    }
+
+    /// Not yet merged part of every set.
+    fn remaining_sets(&self) -> impl Iterator<Item = &[T]> {
+        self.sets
+            .iter()
+            .zip(&self.heads)
+            .map(|(set, head)| &set[*head..])
+            .filter(|set| !set.is_empty())
+    }
+}
+
+impl<T: Key> Sets<T> {
+    /// Describe why no candidate could be picked while linearizing `base`.
+    pub fn conflict(&self, base: &T) -> MroConflict {
+        let mut conflicting: Vec<String> = vec![];
+        for candidate in self.candidates() {
+            let candidate = candidate.label();
+            if !conflicting.contains(&candidate) {
+                conflicting.push(candidate);
+            }
         }
-        self.sets.retain(|set| !set.is_empty());
+        MroConflict {
+            class: base.label(),
+            conflicting,
+            remaining: self
+                .remaining_sets()
+                .map(|set| set.iter().map(T::label).collect())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sets;
+    use crate::C3Error;
+
+    #[test]
+    fn test_find_solution() {
+        let mut sets: Sets<&str> = Sets::new();
+        sets.push(vec!["B", "O"]).unwrap();
+        sets.push(vec!["A", "B"]).unwrap();
+        assert_eq!(sets.push(vec![]), Err(C3Error::PushingEmptySet));
+
+        assert_eq!(sets.find_solution(), Ok("A"));
+        assert_eq!(sets.find_solution(), Ok("B"));
+        assert!(!sets.is_empty());
+        assert_eq!(sets.find_solution(), Ok("O"));
+        assert!(sets.is_empty());
+        assert_eq!(sets.find_solution(), Err(C3Error::NoMoreCandidates));
     }
 }
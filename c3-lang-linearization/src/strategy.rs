@@ -0,0 +1,163 @@
+use super::{c3_linearization::merge, C3Error, Key, Sets, C3};
+use alloc::{vec, vec::Vec};
+
+/// Algorithm that computes the path of a single class.
+pub trait LinearizationStrategy {
+    /// Compute the path of `base`. `input` holds declared parents of all classes
+    /// and `solved` holds already computed paths of all ancestors of `base`.
+    fn linearize<K: Key, M: Key>(
+        &self,
+        base: &K,
+        input: &C3<K, M>,
+        solved: &C3<K, M>,
+    ) -> Result<Vec<K>, C3Error>;
+}
+
+/// C3 linearization, parents are listed from the most derived to the most base-like.
+pub struct C3Merge;
+
+impl LinearizationStrategy for C3Merge {
+    fn linearize<K: Key, M: Key>(
+        &self,
+        base: &K,
+        input: &C3<K, M>,
+        solved: &C3<K, M>,
+    ) -> Result<Vec<K>, C3Error> {
+        let sets = solved.sets_for(input.path(base)?)?;
+        merge(base, sets)
+    }
+}
+
+/// Classic depth-first, left-to-right linearization known from Python 2.
+/// Only the first occurrence of every class is kept. It never fails.
+pub struct DepthFirst;
+
+impl LinearizationStrategy for DepthFirst {
+    fn linearize<K: Key, M: Key>(
+        &self,
+        base: &K,
+        input: &C3<K, M>,
+        solved: &C3<K, M>,
+    ) -> Result<Vec<K>, C3Error> {
+        let mut path = vec![base.clone()];
+        for parent in input.path(base)? {
+            for class in solved.path(&parent)? {
+                if !path.contains(&class) {
+                    path.push(class);
+                }
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// Dylan/L*LOOPS style linearization. It merges the same sets as C3,
+/// but when there is more than one candidate, it follows the CLOS rule:
+/// the candidate that is a direct parent of the rightmost class
+/// in the path computed so far wins.
+pub struct Dylan;
+
+impl LinearizationStrategy for Dylan {
+    fn linearize<K: Key, M: Key>(
+        &self,
+        base: &K,
+        input: &C3<K, M>,
+        solved: &C3<K, M>,
+    ) -> Result<Vec<K>, C3Error> {
+        let mut sets: Sets<K> = solved.sets_for(input.path(base)?)?;
+        let mut path = vec![base.clone()];
+        while !sets.is_empty() {
+            let mut next = None;
+            for class in path.iter().rev() {
+                next = input
+                    .path(class)?
+                    .into_iter()
+                    .find(|parent| sets.is_candidate(parent));
+                if next.is_some() {
+                    break;
+                }
+            }
+            match next {
+                Some(next) => {
+                    sets.remove_solution(&next);
+                    path.push(next);
+                }
+                None => return Err(C3Error::InconsistentMro(sets.conflict(base))),
+            }
+        }
+        Ok(path)
+    }
+}
+
+/// C3 linearization with parents listed the Solidity way,
+/// from the most base-like to the most derived.
+pub struct Solidity;
+
+impl LinearizationStrategy for Solidity {
+    fn linearize<K: Key, M: Key>(
+        &self,
+        base: &K,
+        input: &C3<K, M>,
+        solved: &C3<K, M>,
+    ) -> Result<Vec<K>, C3Error> {
+        let mut parents = input.path(base)?;
+        parents.reverse();
+        let sets = solved.sets_for(parents)?;
+        merge(base, sets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{diamond, panes},
+        linearization_with, Class,
+    };
+
+    fn path<S: LinearizationStrategy>(input: C3, strategy: &S, class: &str) -> String {
+        let output = linearization_with(input, strategy).unwrap();
+        let path: Vec<String> = output
+            .path(&Class::from(class))
+            .unwrap()
+            .iter()
+            .map(Class::to_string)
+            .collect();
+        path.join(", ")
+    }
+
+    #[test]
+    fn test_c3_merge() {
+        assert_eq!(path(diamond(), &C3Merge, "D"), "D, B, C, A");
+        assert_eq!(
+            path(panes(), &C3Merge, "EditableScrollablePane"),
+            "EditableScrollablePane, ScrollablePane, EditablePane, Pane, ScrollingMixin, EditingMixin"
+        );
+    }
+
+    #[test]
+    fn test_depth_first() {
+        assert_eq!(path(diamond(), &DepthFirst, "D"), "D, B, A, C");
+    }
+
+    #[test]
+    fn test_dylan() {
+        assert_eq!(path(diamond(), &Dylan, "D"), "D, B, C, A");
+        assert_eq!(
+            path(panes(), &Dylan, "EditableScrollablePane"),
+            "EditableScrollablePane, ScrollablePane, EditablePane, Pane, EditingMixin, ScrollingMixin"
+        );
+    }
+
+    #[test]
+    fn test_solidity() {
+        assert_eq!(path(diamond(), &Solidity, "D"), "D, C, B, A");
+
+        let mut input = C3::new();
+        input.add_class_str("A", "").unwrap();
+        input.add_class_str("B", "A").unwrap();
+        input.add_class_str("C", "B, A").unwrap();
+        let error = linearization_with(input, &Solidity).unwrap_err();
+        assert!(matches!(error, C3Error::InconsistentMro(_)));
+    }
+}
@@ -0,0 +1,227 @@
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+
+use super::{
+    c3_linearization::{linearize_ancestors, merge},
+    join, C3Error, Key, C3,
+};
+
+/// Reorderings are tried only for classes with at most this many parents.
+const MAX_REORDERED: usize = 8;
+
+/// Removals of at most this many parents at once are tried.
+const MAX_REMOVED: usize = 3;
+
+/// Change of the parent list that makes a class linearizable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix<K> {
+    pub class: K,
+    pub change: Change<K>,
+    /// Parent list after the change.
+    pub parents: Vec<K>,
+    /// Path of the class after the change.
+    pub path: Vec<K>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<K> {
+    Reorder,
+    Remove(Vec<K>),
+}
+
+/// Propose the smallest change of the parent list that makes the class linearizable.
+/// Reorderings keep all parents, so they are tried first, with the fewest swapped pairs.
+/// Then parents are removed, as few as possible. Ancestors must be linearizable.
+/// Returns `None` if the class is already linearizable or no change within the limits helps.
+pub fn suggest_fix<K: Key, M: Key>(input: &C3<K, M>, class: &K) -> Result<Option<Fix<K>>, C3Error> {
+    let parents = input.path(class)?;
+    let solved = linearize_ancestors(input, class)?;
+    let try_parents = |parents: &[K]| -> Result<Option<Vec<K>>, C3Error> {
+        match merge(class, solved.sets_for(parents.to_vec())?) {
+            Ok(path) => Ok(Some(path)),
+            Err(C3Error::InconsistentMro(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    };
+    if try_parents(&parents)?.is_some() {
+        return Ok(None);
+    }
+
+    if parents.len() <= MAX_REORDERED {
+        let mut orders = permutations(parents.len());
+        orders.sort_by_key(|order| inversions(order));
+        for order in orders {
+            let reordered: Vec<K> = order.iter().map(|index| parents[*index].clone()).collect();
+            if let Some(path) = try_parents(&reordered)? {
+                return Ok(Some(Fix {
+                    class: class.clone(),
+                    change: Change::Reorder,
+                    parents: reordered,
+                    path,
+                }));
+            }
+        }
+    }
+
+    for count in 1..=parents.len().min(MAX_REMOVED) {
+        let mut removed: Vec<usize> = (0..count).collect();
+        loop {
+            let kept: Vec<K> = (0..parents.len())
+                .filter(|index| !removed.contains(index))
+                .map(|index| parents[index].clone())
+                .collect();
+            if let Some(path) = try_parents(&kept)? {
+                return Ok(Some(Fix {
+                    class: class.clone(),
+                    change: Change::Remove(
+                        removed
+                            .iter()
+                            .map(|index| parents[*index].clone())
+                            .collect(),
+                    ),
+                    parents: kept,
+                    path,
+                }));
+            }
+            if !next_combination(&mut removed, parents.len()) {
+                break;
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// All orders of `0..n`, in the lexicographic order.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut result = vec![];
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|x| if x >= first { x + 1 } else { x }));
+            result.push(order);
+        }
+    }
+    result
+}
+
+/// Number of pairs swapped by the order.
+fn inversions(order: &[usize]) -> usize {
+    let mut count = 0;
+    for (index, a) in order.iter().enumerate() {
+        count += order[index + 1..].iter().filter(|b| a > b).count();
+    }
+    count
+}
+
+/// Advance a sorted subset of `0..n` to the next one of the same size,
+/// in the lexicographic order. Returns `false` if it was the last one.
+fn next_combination(subset: &mut [usize], n: usize) -> bool {
+    let size = subset.len();
+    for index in (0..size).rev() {
+        if subset[index] < n - size + index {
+            subset[index] += 1;
+            for next in index + 1..size {
+                subset[next] = subset[next - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+impl<K: Key> Display for Fix<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            Change::Reorder => write!(
+                f,
+                "declare parents of {} as {}",
+                self.class.label(),
+                join(&self.parents)
+            )?,
+            Change::Remove(removed) => write!(
+                f,
+                "remove {} from parents of {}",
+                join(removed),
+                self.class.label()
+            )?,
+        }
+        write!(f, " to get path {}", join(&self.path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::classes, Class};
+
+    #[test]
+    fn test_reorder() {
+        let mut input = C3::new();
+        input.add_class_str("O", "").unwrap();
+        input.add_class_str("A", "O").unwrap();
+        input.add_class_str("B", "A").unwrap();
+        input.add_class_str("C", "O").unwrap();
+        input.add_class_str("Z", "A, C, B").unwrap();
+
+        let fix = suggest_fix(&input, &Class::from("Z")).unwrap().unwrap();
+        assert_eq!(
+            fix,
+            Fix {
+                class: Class::from("Z"),
+                change: Change::Reorder,
+                parents: classes(&["C", "B", "A"]),
+                path: classes(&["Z", "C", "B", "A", "O"]),
+            }
+        );
+        assert_eq!(
+            fix.to_string(),
+            "declare parents of Z as C, B, A to get path Z, C, B, A, O"
+        );
+        assert_eq!(suggest_fix(&input, &Class::from("B")), Ok(None));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut input = C3::new();
+        input.add_class_str("A", "").unwrap();
+        input.add_class_str("B", "").unwrap();
+        input.add_class_str("X", "A, B").unwrap();
+        input.add_class_str("Y", "B, A").unwrap();
+        input.add_class_str("Z", "X, Y").unwrap();
+
+        let fix = suggest_fix(&input, &Class::from("Z")).unwrap().unwrap();
+        assert_eq!(fix.change, Change::Remove(classes(&["X"])));
+        assert_eq!(
+            fix.to_string(),
+            "remove X from parents of Z to get path Z, Y, B, A"
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut input = C3::new();
+        input.add_class_str("A", "").unwrap();
+        input.add_class_str("B", "").unwrap();
+        let mut parents = vec![];
+        for index in 0..8 {
+            input.add_class_str(&format!("X{}", index), "A, B").unwrap();
+            input.add_class_str(&format!("Y{}", index), "B, A").unwrap();
+            parents.push(format!("X{}, Y{}", index, index));
+        }
+        input.add_class_str("Z", &parents.join(", ")).unwrap();
+        assert_eq!(suggest_fix(&input, &Class::from("Z")), Ok(None));
+
+        let mut subset = vec![0, 1];
+        let mut subsets = vec![subset.clone()];
+        while next_combination(&mut subset, 4) {
+            subsets.push(subset.clone());
+        }
+        assert_eq!(
+            subsets,
+            vec![[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]]
+        );
+    }
+}
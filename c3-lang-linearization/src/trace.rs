@@ -0,0 +1,208 @@
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display};
+
+use super::{c3_linearization::linearize_ancestors, join, C3Error, Key, Sets, C3};
+
+/// Full derivation of a merge, step by step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeTrace<T> {
+    pub base: T,
+    pub steps: Vec<MergeStep<T>>,
+    pub result: Result<Vec<T>, C3Error>,
+}
+
+/// Single step of a merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeStep<T> {
+    /// Not yet merged part of every set, in the order the sets were pushed.
+    pub sets: Vec<Vec<T>>,
+    /// Heads considered before a solution was found.
+    pub rejected: Vec<Rejection<T>>,
+    /// Picked head. `None` if every head was rejected.
+    pub chosen: Option<T>,
+}
+
+/// Head that could not be picked, because it's in the tail of another set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection<T> {
+    pub candidate: T,
+    /// Index of the set that has the candidate in its tail.
+    pub blocked_by: usize,
+}
+
+/// Same as `merge`, but records every step.
+pub fn merge_trace<T: Key>(base: &T, mut sets: Sets<T>) -> MergeTrace<T> {
+    let mut steps = vec![];
+    let mut solutions = vec![base.clone()];
+    while !sets.is_empty() {
+        let snapshot = sets.snapshot();
+        let mut rejected: Vec<Rejection<T>> = vec![];
+        let mut chosen = None;
+        for set in &snapshot {
+            let candidate = match set.first() {
+                Some(candidate) => candidate,
+                None => continue,
+            };
+            if rejected.iter().any(|r| &r.candidate == candidate) {
+                continue;
+            }
+            match snapshot
+                .iter()
+                .position(|set| set[1.min(set.len())..].contains(candidate))
+            {
+                Some(blocked_by) => rejected.push(Rejection {
+                    candidate: candidate.clone(),
+                    blocked_by,
+                }),
+                None => {
+                    chosen = Some(candidate.clone());
+                    break;
+                }
+            }
+        }
+        let result = match &chosen {
+            Some(chosen) => {
+                sets.remove_solution(chosen);
+                solutions.push(chosen.clone());
+                None
+            }
+            None => Some(Err(C3Error::InconsistentMro(sets.conflict(base)))),
+        };
+        steps.push(MergeStep {
+            sets: snapshot,
+            rejected,
+            chosen,
+        });
+        if let Some(result) = result {
+            return MergeTrace {
+                base: base.clone(),
+                steps,
+                result,
+            };
+        }
+    }
+    MergeTrace {
+        base: base.clone(),
+        steps,
+        result: Ok(solutions),
+    }
+}
+
+/// Explain the path of a single class from the input hierarchy.
+/// Only ancestors of the class must be linearizable.
+pub fn explain<K: Key, M: Key>(input: &C3<K, M>, class: &K) -> Result<MergeTrace<K>, C3Error> {
+    let parents = input.path(class)?;
+    let solved = linearize_ancestors(input, class)?;
+    let sets = solved.sets_for(parents)?;
+    Ok(merge_trace(class, sets))
+}
+
+impl<T: Key> Display for MergeTrace<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Linearization of {}", self.base.label())?;
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(f, "Step {}:", index + 1)?;
+            for (index, set) in step.sets.iter().enumerate() {
+                if !set.is_empty() {
+                    writeln!(f, "  set {}: [{}]", index + 1, join(set))?;
+                }
+            }
+            for rejection in &step.rejected {
+                writeln!(
+                    f,
+                    "  {} rejected, it's in the tail of set {}",
+                    rejection.candidate.label(),
+                    rejection.blocked_by + 1
+                )?;
+            }
+            match &step.chosen {
+                Some(chosen) => writeln!(f, "  {} chosen", chosen.label())?,
+                None => writeln!(f, "  no candidate left")?,
+            }
+        }
+        match &self.result {
+            Ok(path) => write!(f, "Result: {}", join(path)),
+            Err(error) => write!(f, "Error: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Class;
+
+    #[test]
+    fn test_merge_trace() {
+        let mut sets: Sets<&str> = Sets::new();
+        sets.push(vec!["B", "O"]).unwrap();
+        sets.push(vec!["A", "B", "O"]).unwrap();
+        sets.push(vec!["B", "A"]).unwrap();
+
+        let trace = merge_trace(&"K", sets);
+        assert_eq!(trace.steps.len(), 1);
+        assert_eq!(
+            trace.steps[0].rejected,
+            vec![
+                Rejection {
+                    candidate: "B",
+                    blocked_by: 1
+                },
+                Rejection {
+                    candidate: "A",
+                    blocked_by: 2
+                }
+            ]
+        );
+        assert_eq!(trace.steps[0].chosen, None);
+        assert!(matches!(trace.result, Err(C3Error::InconsistentMro(_))));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut input = C3::new();
+        input.add_class_str("Context", "").unwrap();
+        input.add_class_str("ERC20", "Context").unwrap();
+        input
+            .add_class_str("ERC20Burnable", "ERC20, Context")
+            .unwrap();
+        input.add_class_str("Ownable", "Context").unwrap();
+        input
+            .add_class_str("Token", "ERC20Burnable, Ownable")
+            .unwrap();
+        input.add_class_str("Broken", "Context, ERC20").unwrap();
+
+        let trace = explain(&input, &Class::from("Token")).unwrap();
+        assert_eq!(
+            trace.to_string(),
+            "Linearization of Token
+Step 1:
+  set 1: [ERC20Burnable, ERC20, Context]
+  set 2: [Ownable, Context]
+  set 3: [ERC20Burnable, Ownable]
+  ERC20Burnable chosen
+Step 2:
+  set 1: [ERC20, Context]
+  set 2: [Ownable, Context]
+  set 3: [Ownable]
+  ERC20 chosen
+Step 3:
+  set 1: [Context]
+  set 2: [Ownable, Context]
+  set 3: [Ownable]
+  Context rejected, it's in the tail of set 2
+  Ownable chosen
+Step 4:
+  set 1: [Context]
+  set 2: [Context]
+  Context chosen
+Result: Token, ERC20Burnable, ERC20, Ownable, Context"
+        );
+
+        let trace = explain(&input, &Class::from("Broken")).unwrap();
+        assert!(trace.to_string().ends_with(
+            "Error: Cannot create a consistent method resolution order (MRO) for class Broken: \
+             conflicting bases Context, ERC20; remaining sets [Context], [ERC20, Context], [Context, ERC20]"
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+use super::{C3Error, Invalid, Key, Problem, C3};
+use alloc::{vec, vec::Vec};
+
+/// Check the input hierarchy before linearizing it.
+/// Reports every problem found, not only the first one.
+pub fn validate<K: Key, M: Key>(input: &C3<K, M>) -> Result<(), C3Error> {
+    let mut problems = vec![];
+    for class in input.all_classes() {
+        let parents = input.path(&class)?;
+        problems.append(&mut check_class(input, &class, &parents));
+    }
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(C3Error::Invalid(problems))
+    }
+}
+
+/// Check a single class and its parents against classes declared in `c3`.
+pub(crate) fn check_class<K: Key, M: Key>(c3: &C3<K, M>, class: &K, parents: &[K]) -> Vec<Invalid> {
+    let mut problems = vec![];
+    let mut report = |problem: Problem| {
+        problems.push(Invalid {
+            class: class.label(),
+            problem,
+        })
+    };
+    if class.label().is_empty() {
+        report(Problem::EmptyName);
+    }
+    for (index, parent) in parents.iter().enumerate() {
+        if parent.label().is_empty() {
+            report(Problem::EmptyParentName);
+        } else if parent == class {
+            report(Problem::OwnParent);
+        } else if parents[..index].contains(parent) {
+            report(Problem::DuplicatedParent(parent.label()));
+        } else if !c3.contains(parent) {
+            report(Problem::UndeclaredParent(parent.label()));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Class;
+
+    fn invalid(class: &str, problem: Problem) -> Invalid {
+        Invalid {
+            class: String::from(class),
+            problem,
+        }
+    }
+
+    #[test]
+    fn test_valid_input() {
+        let mut input = C3::new();
+        input.add_class_str("A", "").unwrap();
+        input.add_class_str("B", "A").unwrap();
+        input.add_class_str("C", "B, A").unwrap();
+        assert_eq!(validate(&input), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let mut input = C3::new();
+        input.add(Class::from(""), vec![]);
+        input.add_class_str("A", "A").unwrap();
+        input.add_class_str("B", "X, A, A").unwrap();
+        input.add(Class::from("C"), vec![Class::from(""), Class::from("A")]);
+
+        let error = validate(&input).unwrap_err();
+        let target = vec![
+            invalid("", Problem::EmptyName),
+            invalid("A", Problem::OwnParent),
+            invalid("B", Problem::UndeclaredParent(String::from("X"))),
+            invalid("B", Problem::DuplicatedParent(String::from("A"))),
+            invalid("C", Problem::EmptyParentName),
+        ];
+        assert_eq!(error, C3Error::Invalid(target));
+        assert_eq!(
+            error.to_string(),
+            "Invalid hierarchy: a class has an empty name; A is its own parent; \
+             B has undeclared parent X; B lists parent A more than once; \
+             C has a parent with an empty name"
+        );
+    }
+}
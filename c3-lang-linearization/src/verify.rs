@@ -0,0 +1,378 @@
+use alloc::collections::BTreeMap;
+use alloc::{vec, vec::Vec};
+
+use super::{validate, C3Error, Key, Rule, Violation, C3};
+
+/// Check that paths in `output` meet the C3 guarantees for the hierarchy declared in `input`:
+/// parents keep their local precedence order, every path extends paths of its parents
+/// and respects the extended precedence graph.
+/// Paths are not recomputed, so `output` can come from any source.
+pub fn verify<K: Key, M: Key>(input: &C3<K, M>, output: &C3<K, M>) -> Result<(), C3Error> {
+    validate(input)?;
+    let lineages = Lineages::new(input)?;
+    let mut violations = vec![];
+    for class in input.all_classes() {
+        if let Some(rule) = lineages.verify_class(output, &class) {
+            violations.push(Violation {
+                class: class.label(),
+                rule,
+            });
+        }
+    }
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(C3Error::Violated(violations))
+    }
+}
+
+/// Classes of the input numbered in key order, with their ancestors and descendants.
+/// Built once and shared by checks of all classes.
+struct Lineages<K> {
+    numbers: BTreeMap<K, usize>,
+    keys: Vec<K>,
+    parents: Vec<Vec<usize>>,
+    /// The class followed by its ancestors.
+    hierarchies: Vec<Vec<usize>>,
+    /// The class and its ancestors.
+    lineages: Vec<Bits>,
+    /// The class and its descendants.
+    descendants: Vec<Bits>,
+}
+
+impl<K: Key> Lineages<K> {
+    fn new<M: Key>(input: &C3<K, M>) -> Result<Self, C3Error> {
+        let mut keys = input.all_classes();
+        keys.sort();
+        let size = keys.len();
+        let numbers: BTreeMap<K, usize> = keys
+            .iter()
+            .enumerate()
+            .map(|(number, class)| (class.clone(), number))
+            .collect();
+        let mut parents = vec![];
+        let mut hierarchies = vec![];
+        let mut lineages = vec![];
+        let mut descendants = vec![Bits::new(size); size];
+        for (number, class) in keys.iter().enumerate() {
+            parents.push(input.path(class)?.iter().map(|x| numbers[x]).collect());
+            let mut hierarchy = vec![number];
+            hierarchy.extend(input.ancestors(class)?.iter().map(|x| numbers[x]));
+            let mut lineage = Bits::new(size);
+            for &ancestor in &hierarchy {
+                lineage.insert(ancestor);
+                descendants[ancestor].insert(number);
+            }
+            hierarchies.push(hierarchy);
+            lineages.push(lineage);
+        }
+        Ok(Lineages {
+            numbers,
+            keys,
+            parents,
+            hierarchies,
+            lineages,
+            descendants,
+        })
+    }
+
+    /// Return the first broken rule. Later rules assume the earlier ones hold.
+    fn verify_class<M: Key>(&self, output: &C3<K, M>, class: &K) -> Option<Rule> {
+        let path = match output.path(class) {
+            Ok(path) => path,
+            Err(_) => return Some(Rule::Missing),
+        };
+        let number = self.numbers[class];
+        let mut position = vec![0; self.keys.len()];
+        let mut listed = Bits::new(self.keys.len());
+        for (index, x) in path.iter().enumerate() {
+            match self.numbers.get(x) {
+                Some(&x) if self.lineages[number].contains(x) && !listed.contains(x) => {
+                    listed.insert(x);
+                    position[x] = index;
+                }
+                _ => return Some(Rule::Malformed),
+            }
+        }
+        if path.first() != Some(class) || path.len() != self.hierarchies[number].len() {
+            return Some(Rule::Malformed);
+        }
+
+        let parents = &self.parents[number];
+        for (index, &first) in parents.iter().enumerate() {
+            for &second in &parents[index + 1..] {
+                if position[first] > position[second] {
+                    return Some(Rule::LocalPrecedence(
+                        self.keys[first].label(),
+                        self.keys[second].label(),
+                    ));
+                }
+            }
+        }
+
+        for &parent in parents {
+            let parent = &self.keys[parent];
+            if let Ok(parent_path) = output.path(parent) {
+                let mut rest = path.iter();
+                if !parent_path.iter().all(|x| rest.any(|y| x == y)) {
+                    return Some(Rule::Monotonicity(parent.label()));
+                }
+            }
+        }
+
+        for (first, second) in self.extended_precedence(number) {
+            if position[first] > position[second] {
+                return Some(Rule::ExtendedPrecedence(
+                    self.keys[first].label(),
+                    self.keys[second].label(),
+                ));
+            }
+        }
+        None
+    }
+
+    /// Edges of the extended precedence graph of the class.
+    /// Every class in the hierarchy precedes its parents and parents keep the declaration order.
+    /// When a parent precedes another one, these edges are extended to their ancestors
+    /// that are not ordered yet and have no common subclass below the class declaring the parents.
+    /// Extended edges that form a cycle contradict each other, so they are left out.
+    fn extended_precedence(&self, class: usize) -> Vec<(usize, usize)> {
+        let size = self.keys.len();
+        let hierarchy = &self.hierarchies[class];
+        let mut edges = vec![];
+        for &ancestor in hierarchy {
+            let parents = &self.parents[ancestor];
+            for (index, &parent) in parents.iter().enumerate() {
+                edges.push((ancestor, parent));
+                if let Some(&next) = parents.get(index + 1) {
+                    edges.push((parent, next));
+                }
+            }
+        }
+
+        // Subclasses always reach their ancestors, so only other classes can be unordered.
+        let reach = closure(size, hierarchy, &edges);
+        let mut extended = vec![];
+        for &a in hierarchy {
+            let unordered = self.lineages[class]
+                .difference(&reach[a])
+                .difference(&self.descendants[a]);
+            for b in unordered.iter() {
+                if !reach[b].contains(a) && self.declares_apart(hierarchy, a, b) {
+                    extended.push((a, b));
+                }
+            }
+        }
+
+        let all: Vec<(usize, usize)> = edges.iter().chain(&extended).cloned().collect();
+        let reach = closure(size, hierarchy, &all);
+        edges.extend(extended.into_iter().filter(|&(a, b)| !reach[b].contains(a)));
+        edges
+    }
+
+    /// Check if a class of the hierarchy declares a parent inheriting from `a` before
+    /// a parent inheriting from `b`, and no class between it and the parents inherits from both.
+    fn declares_apart(&self, hierarchy: &[usize], a: usize, b: usize) -> bool {
+        let common = self.descendants[a].intersection(&self.descendants[b]);
+        hierarchy.iter().any(|&declaring| {
+            let parents = &self.parents[declaring];
+            common.contains(declaring)
+                && self.lineages[declaring].intersection(&common).len() == 1
+                && parents.iter().enumerate().any(|(index, &first)| {
+                    self.descendants[a].contains(first)
+                        && parents[index + 1..]
+                            .iter()
+                            .any(|&second| self.descendants[b].contains(second))
+                })
+        })
+    }
+}
+
+/// Classes reachable from every class of the hierarchy through the edges.
+fn closure(size: usize, hierarchy: &[usize], edges: &[(usize, usize)]) -> Vec<Bits> {
+    let mut next = vec![vec![]; size];
+    for &(from, to) in edges {
+        next[from].push(to);
+    }
+    // Visit parents before their children, so one pass is enough without cycles.
+    let mut visited = Bits::new(size);
+    let mut order = vec![];
+    for &start in hierarchy {
+        if visited.contains(start) {
+            continue;
+        }
+        visited.insert(start);
+        let mut stack = vec![(start, 0)];
+        while let Some((class, index)) = stack.pop() {
+            match next[class].get(index) {
+                Some(&parent) => {
+                    stack.push((class, index + 1));
+                    if !visited.contains(parent) {
+                        visited.insert(parent);
+                        stack.push((parent, 0));
+                    }
+                }
+                None => order.push(class),
+            }
+        }
+    }
+
+    let mut reach = vec![Bits::new(size); size];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &class in &order {
+            for &parent in &next[class] {
+                let reached = core::mem::take(&mut reach[parent]);
+                changed |= reach[class].union(&reached);
+                reach[parent] = reached;
+                if !reach[class].contains(parent) {
+                    reach[class].insert(parent);
+                    changed = true;
+                }
+            }
+        }
+    }
+    reach
+}
+
+/// Set of class numbers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(size: usize) -> Self {
+        Bits(vec![0; size.div_ceil(64)])
+    }
+
+    fn insert(&mut self, number: usize) {
+        self.0[number / 64] |= 1 << (number % 64);
+    }
+
+    fn contains(&self, number: usize) -> bool {
+        self.0[number / 64] & (1 << (number % 64)) != 0
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Add numbers of the other set. Return if any was missing.
+    fn union(&mut self, other: &Bits) -> bool {
+        let mut changed = false;
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            changed |= *other & !*word != 0;
+            *word |= other;
+        }
+        changed
+    }
+
+    fn intersection(&self, other: &Bits) -> Bits {
+        Bits(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+    }
+
+    fn difference(&self, other: &Bits) -> Bits {
+        Bits(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(index, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| index * 64 + bit)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        c3_linearization,
+        fixtures::{diamond, panes},
+        linearization_with, DepthFirst, Dylan,
+    };
+
+    fn violation(class: &str, rule: Rule) -> Violation {
+        Violation {
+            class: String::from(class),
+            rule,
+        }
+    }
+
+    #[test]
+    fn test_verify_c3() {
+        let input = panes();
+        let output = c3_linearization(input.clone()).unwrap();
+        assert_eq!(verify(&input, &output), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_other_strategies() {
+        let input = panes();
+        let output = linearization_with(input.clone(), &Dylan).unwrap();
+        let target = vec![violation(
+            "EditableScrollablePane",
+            Rule::ExtendedPrecedence(String::from("ScrollingMixin"), String::from("EditingMixin")),
+        )];
+        assert_eq!(verify(&input, &output), Err(C3Error::Violated(target)));
+
+        let input = diamond();
+        let output = linearization_with(input.clone(), &DepthFirst).unwrap();
+        let error = verify(&input, &output).unwrap_err();
+        assert_eq!(
+            error,
+            C3Error::Violated(vec![violation("D", Rule::Monotonicity(String::from("C")))])
+        );
+        assert_eq!(
+            error.to_string(),
+            "Linearization breaks C3 guarantees: path of D does not keep the order of the path of C"
+        );
+    }
+
+    #[test]
+    fn test_verify_large() {
+        // Every class inherits from three previous ones, so lineages overlap a lot.
+        let mut input: C3<u32> = C3::new();
+        for class in 0..300 {
+            input.add(class, (class.saturating_sub(3)..class).rev().collect());
+        }
+        let mut output = c3_linearization(input.clone()).unwrap();
+        assert_eq!(verify(&input, &output), Ok(()));
+
+        let mut path = output.path(&299).unwrap();
+        path.swap(1, 2);
+        output.add(299, path);
+        let target = vec![violation(
+            "299",
+            Rule::LocalPrecedence(String::from("298"), String::from("297")),
+        )];
+        assert_eq!(verify(&input, &output), Err(C3Error::Violated(target)));
+    }
+
+    #[test]
+    fn test_verify_hand_written() {
+        let mut input = C3::new();
+        input.add_class_str("A", "").unwrap();
+        input.add_class_str("B", "").unwrap();
+        input.add_class_str("C", "A, B").unwrap();
+        input.add_class_str("D", "C").unwrap();
+        input.add_class_str("E", "A").unwrap();
+
+        let mut output = C3::new();
+        output.add_class_str("A", "A").unwrap();
+        output.add_class_str("B", "B").unwrap();
+        output.add_class_str("C", "C, B, A").unwrap();
+        output.add_class_str("D", "D, C, A").unwrap();
+
+        let target = vec![
+            violation(
+                "C",
+                Rule::LocalPrecedence(String::from("A"), String::from("B")),
+            ),
+            violation("D", Rule::Malformed),
+            violation("E", Rule::Missing),
+        ];
+        assert_eq!(verify(&input, &output), Err(C3Error::Violated(target)));
+    }
+}
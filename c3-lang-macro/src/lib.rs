@@ -1,10 +1,18 @@
 use c3_lang_parser::RustPackageDef;
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::ToTokens;
 
 #[proc_macro]
 pub fn c3_lang(item: TokenStream) -> TokenStream {
-    let rust_ast: RustPackageDef = syn::parse2(item.into()).unwrap();
-    let c3_ast = c3_lang_parser::build_package_def(&rust_ast);
-    c3_ast.to_token_stream().into()
+    let rust_ast: RustPackageDef = match syn::parse2(item.into()) {
+        Ok(rust_ast) => rust_ast,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    match c3_lang_parser::build_package_def(&rust_ast) {
+        Ok(c3_ast) => c3_ast.to_token_stream().into(),
+        Err(error) => syn::Error::new(Span::call_site(), error)
+            .to_compile_error()
+            .into(),
+    }
 }
@@ -1,79 +1,83 @@
 use crate::{Register, RustClassDef, RustPackageDef};
-use c3_lang_linearization::{c3_linearization, Class, Fn, C3};
+use c3_lang_linearization::{c3_linearization, C3Error, Class, Fn, C3};
 use syn::{FnArg, ImplItemMethod, ReturnType};
 
 use super::c3_ast::{ClassDef, ClassFnImpl, ClassNameDef, FnDef, PackageDef, VarDef};
 
 // --- Package Builder ---
 
-pub fn build_package_def(rust_package: &RustPackageDef) -> PackageDef {
+pub fn build_package_def(rust_package: &RustPackageDef) -> Result<PackageDef, C3Error> {
     let other_code = rust_package.other_code.clone();
-    let class_name = build_class_name_def(rust_package);
-    let classes = build_classes(rust_package);
+    let class_name = build_class_name_def(rust_package)?;
+    let classes = build_classes(rust_package)?;
 
-    PackageDef {
+    Ok(PackageDef {
         other_code,
         class_name,
         classes,
-    }
+    })
 }
 
 // --- Top Level Builders ---
 
-fn build_class_name_def(rust_package: &RustPackageDef) -> ClassNameDef {
-    ClassNameDef {
+fn build_class_name_def(rust_package: &RustPackageDef) -> Result<ClassNameDef, C3Error> {
+    Ok(ClassNameDef {
         classes: rust_package
             .classes
             .iter()
             .map(RustClassDef::class)
-            .collect(),
-    }
+            .collect::<Result<_, _>>()?,
+    })
 }
 
-fn build_classes(rust_package: &RustPackageDef) -> Vec<ClassDef> {
-    let c3 = build_c3(rust_package);
-    let register = build_register(rust_package);
+fn build_classes(rust_package: &RustPackageDef) -> Result<Vec<ClassDef>, C3Error> {
+    let c3 = build_c3(rust_package)?;
+    let register = build_register(rust_package)?;
     let mut classes = vec![];
     for class in &rust_package.classes {
         if class.is_public() {
-            classes.push(build_class(class, &c3, &register));
+            classes.push(build_class(class, &c3, &register)?);
         }
     }
-    classes
+    Ok(classes)
 }
 
-fn build_class(rust_class: &RustClassDef, c3: &C3, register: &Register) -> ClassDef {
-    let class = rust_class.class();
-    let variables = build_variables(&class, c3, register);
-    let functions = build_functions(&class, c3, register);
-    ClassDef {
+fn build_class(
+    rust_class: &RustClassDef,
+    c3: &C3,
+    register: &Register,
+) -> Result<ClassDef, C3Error> {
+    let class = rust_class.class()?;
+    let variables = build_variables(&class, c3, register)?;
+    let functions = build_functions(&class, c3, register)?;
+    Ok(ClassDef {
         struct_attrs: rust_class.struct_attrs(),
         impl_attrs: rust_class.impl_attrs(),
         class: class.clone(),
-        path: c3.path(&class).unwrap(),
+        path: c3.path(&class)?,
         variables,
         functions,
-    }
+    })
 }
 
-fn build_variables(class: &Class, c3: &C3, register: &Register) -> Vec<VarDef> {
+fn build_variables(class: &Class, c3: &C3, register: &Register) -> Result<Vec<VarDef>, C3Error> {
     let mut variables = vec![];
-    for variable in c3.variables(class) {
+    for variable in c3.variables(class)? {
         let field = register.get_var(variable);
         variables.push(VarDef {
             ident: field.ident.unwrap().clone(),
             ty: field.ty,
         });
     }
-    variables
+    Ok(variables)
 }
 
-fn build_functions(class: &Class, c3: &C3, register: &Register) -> Vec<FnDef> {
+fn build_functions(class: &Class, c3: &C3, register: &Register) -> Result<Vec<FnDef>, C3Error> {
     let mut functions = vec![];
-    for function in &c3.functions(class) {
+    for function in &c3.functions(class)? {
         functions.push(build_function(function, register));
     }
-    functions
+    Ok(functions)
 }
 
 fn build_function(fun: &Fn, register: &Register) -> FnDef {
@@ -112,30 +116,30 @@ fn get_class_function_impls(fun: &Fn, register: &Register) -> Vec<ClassFnImpl> {
     result
 }
 
-fn build_c3(rust_package: &RustPackageDef) -> C3 {
+fn build_c3(rust_package: &RustPackageDef) -> Result<C3, C3Error> {
     let mut input = C3::new();
     for class in &rust_package.classes {
-        input.add(class.class(), class.parents());
+        input.add(class.class()?, class.parents()?);
     }
-    let mut output = c3_linearization(input).unwrap();
+    let mut output = c3_linearization(input)?;
     for class in &rust_package.classes {
-        output.register_fns(class.class(), class.functions());
-        output.register_vars(class.class(), class.variables());
+        output.register_fns(class.class()?, class.functions()?);
+        output.register_vars(class.class()?, class.variables()?);
     }
-    output
+    Ok(output)
 }
 
-fn build_register(rust_package: &RustPackageDef) -> Register {
+fn build_register(rust_package: &RustPackageDef) -> Result<Register, C3Error> {
     let mut register = Register::default();
     for class in &rust_package.classes {
-        for (name, implementation) in class.function_impls() {
-            register.add(class.class(), name, implementation);
+        for (name, implementation) in class.function_impls()? {
+            register.add(class.class()?, name, implementation);
         }
-        for (var, field) in class.variables_impl() {
+        for (var, field) in class.variables_impl()? {
             register.add_var(var, field);
         }
     }
-    register
+    Ok(register)
 }
 
 #[cfg(test)]
@@ -271,8 +275,29 @@ pub mod tests {
 
     #[test]
     fn test_building_package() {
-        let result = build_package_def(&test_c3_code());
+        let result = build_package_def(&test_c3_code()).unwrap();
         let target = test_c3_ast();
         test_structs(result, target);
     }
+
+    #[test]
+    fn test_building_inconsistent_package() {
+        let input: RustPackageDef = parse_quote! {
+            pub struct A {}
+            impl A {
+                pub const PARENTS: &'static [ClassName; 1] = &[ClassName::B];
+            }
+
+            pub struct B {}
+            impl B {
+                pub const PARENTS: &'static [ClassName; 1] = &[ClassName::A];
+            }
+        };
+        let error = build_package_def(&input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot linearize classes: A is part of cycle A -> B -> A; \
+             B is part of cycle B -> A -> B"
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use c3_lang_linearization::{Class, Fn, Var};
+use c3_lang_linearization::{C3Error, Class, Fn, Var};
 use quote::format_ident;
 use syn::{
     parse::Parse, punctuated::Punctuated, Attribute, Expr, Field, Fields, ImplItem, ImplItemConst,
@@ -30,8 +30,8 @@ impl Parse for RustClassDef {
 }
 
 impl RustClassDef {
-    pub fn class(&self) -> Class {
-        Class::from(self.item_struct.ident.to_string())
+    pub fn class(&self) -> Result<Class, C3Error> {
+        Class::new(&self.item_struct.ident.to_string())
     }
 
     pub fn is_public(&self) -> bool {
@@ -49,9 +49,9 @@ impl RustClassDef {
         }
     }
 
-    pub fn parents(&self) -> Vec<Class> {
+    pub fn parents(&self) -> Result<Vec<Class>, C3Error> {
         if self.item_impl.is_none() {
-            return vec![];
+            return Ok(vec![]);
         }
 
         let item_impl = self.item_impl.clone().unwrap();
@@ -71,7 +71,7 @@ impl RustClassDef {
                                     let path = expr.path.clone();
                                     let segments = path.segments;
                                     if segments[0].ident == format_ident!("ClassName") {
-                                        parents.push(Class::from(segments[1].ident.to_string()));
+                                        parents.push(Class::new(&segments[1].ident.to_string())?);
                                     }
                                 }
                             }
@@ -80,39 +80,39 @@ impl RustClassDef {
                 }
             }
         }
-        parents
+        Ok(parents)
     }
 
-    pub fn functions(&self) -> Vec<Fn> {
-        self.function_impls().into_iter().map(|x| x.0).collect()
+    pub fn functions(&self) -> Result<Vec<Fn>, C3Error> {
+        Ok(self.function_impls()?.into_iter().map(|x| x.0).collect())
     }
 
-    pub fn function_impls(&self) -> Vec<(Fn, ImplItemMethod)> {
+    pub fn function_impls(&self) -> Result<Vec<(Fn, ImplItemMethod)>, C3Error> {
         let item_impl = self.item_impl.clone().unwrap();
         let items: Vec<ImplItem> = item_impl.items;
         let mut functions: Vec<(Fn, ImplItemMethod)> = vec![];
         for item in items.iter() {
             if let ImplItem::Method(method) = item {
                 let name = method.sig.ident.to_string();
-                functions.push((Fn::from(name), method.clone()));
+                functions.push((Fn::new(&name)?, method.clone()));
             }
         }
-        functions
+        Ok(functions)
     }
 
-    pub fn variables(&self) -> Vec<Var> {
-        self.variables_impl().into_iter().map(|x| x.0).collect()
+    pub fn variables(&self) -> Result<Vec<Var>, C3Error> {
+        Ok(self.variables_impl()?.into_iter().map(|x| x.0).collect())
     }
 
-    pub fn variables_impl(&self) -> Vec<(Var, Field)> {
+    pub fn variables_impl(&self) -> Result<Vec<(Var, Field)>, C3Error> {
         let mut variables: Vec<(Var, Field)> = vec![];
         if let Fields::Named(fields) = &self.item_struct.fields {
             for field in &fields.named {
-                let var = Var::from(field.ident.clone().unwrap().to_string());
+                let var = Var::new(&field.ident.clone().unwrap().to_string())?;
                 variables.push((var, field.clone()));
             }
         };
-        variables
+        Ok(variables)
     }
 }
 
@@ -175,8 +175,11 @@ mod tests {
         };
         let result: RustClassDef = syn::parse2(input).unwrap();
         assert!(result.is_public());
-        assert_eq!(result.class(), Class::from("A"));
-        assert_eq!(result.parents(), vec![Class::from("X"), Class::from("Y")]);
+        assert_eq!(result.class(), Ok(Class::from("A")));
+        assert_eq!(
+            result.parents(),
+            Ok(vec![Class::from("X"), Class::from("Y")])
+        );
         assert_eq!(
             result.struct_attrs(),
             vec![parse_quote! { #[derive(Default)] }]
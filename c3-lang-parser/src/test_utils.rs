@@ -33,9 +33,9 @@ pub fn format_rust_expression(value: String) -> String {
                 // (currently 14 from the start and 2 before the end, respectively)
                 let start = PREFIX.len() + 1;
                 let end = output.stdout.len() - SUFFIX.len();
-                return std::str::from_utf8(&output.stdout[start..end])
+                std::str::from_utf8(&output.stdout[start..end])
                     .unwrap()
-                    .to_owned();
+                    .to_owned()
             } else {
                 panic!("not a rust code 3 {}", value);
             }